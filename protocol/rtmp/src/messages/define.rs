@@ -39,9 +39,14 @@ pub enum MessageTypes {
     },
     AudioData {
         data: BytesMut,
+        timestamp: u32,
     },
     VideoData {
         data: BytesMut,
+        timestamp: u32,
+    },
+    Aggregate {
+        data: BytesMut,
     },
 }
 
@@ -66,4 +71,169 @@ pub mod msg_type_id {
     pub const SHARED_OBJ_AMF0: u8 = 19;
 
     pub const AGGREGATE: u8 = 22;
+}
+
+// An aggregate's body is a back-to-back sequence of FLV-tag-shaped sub-messages: an 11-byte
+// header (type id, 24-bit payload length, 24-bit timestamp + an 8-bit extended-timestamp high
+// byte, 24-bit stream id), the payload itself, and a trailing 4-byte "previous tag size"
+// (payload length + 11) that mirrors the FLV container's own tag framing.
+const SUB_MESSAGE_HEADER_LEN: usize = 11;
+const PREVIOUS_TAG_SIZE_LEN: usize = 4;
+
+/// Errors from [`decode_aggregate`].
+#[derive(Debug)]
+pub enum AggregateDecodeError {
+    /// Fewer than [`SUB_MESSAGE_HEADER_LEN`] bytes remained where a sub-message header was
+    /// expected.
+    TruncatedHeader,
+    /// A sub-message's declared payload length (plus its trailing previous-tag-size) doesn't
+    /// fit in what's left of the aggregate body.
+    PayloadOverrun {
+        declared_len: usize,
+        remaining: usize,
+    },
+}
+
+/// Splits an `Aggregate` message's body into its constituent sub-messages, re-dispatching each
+/// one through the same type-id constants used for top-level messages (e.g. [`msg_type_id::AUDIO`]
+/// becomes [`MessageTypes::AudioData`]).
+///
+/// Each sub-message's timestamp is given relative to the first sub-message's timestamp, the
+/// same "base timestamp" convention the RTMP spec uses for aggregates.
+///
+/// Sub-messages whose type id isn't one we otherwise handle are skipped rather than treated as
+/// an error, since an aggregate is still usable with a future/unknown sub-type mixed in.
+///
+/// # Errors
+///
+/// [`AggregateDecodeError`] if a sub-message's header or declared payload length would run
+/// past the end of `data`.
+pub fn decode_aggregate(data: &BytesMut) -> Result<Vec<MessageTypes>, AggregateDecodeError> {
+    let mut messages = Vec::new();
+    let mut base_timestamp: Option<u32> = None;
+    let mut offset = 0;
+
+    while offset < data.len() {
+        if data.len() - offset < SUB_MESSAGE_HEADER_LEN {
+            return Err(AggregateDecodeError::TruncatedHeader);
+        }
+        let header = &data[offset..offset + SUB_MESSAGE_HEADER_LEN];
+        let type_id = header[0];
+        let payload_len =
+            ((header[1] as usize) << 16) | ((header[2] as usize) << 8) | header[3] as usize;
+        let timestamp = ((header[7] as u32) << 24)
+            | ((header[4] as u32) << 16)
+            | ((header[5] as u32) << 8)
+            | header[6] as u32;
+        // Stream id occupies header[8..11] (always 0 in practice for aggregates) and isn't
+        // represented on `MessageTypes` today, so it's parsed-past but not kept.
+        offset += SUB_MESSAGE_HEADER_LEN;
+
+        let remaining = data.len() - offset;
+        if remaining < payload_len + PREVIOUS_TAG_SIZE_LEN {
+            return Err(AggregateDecodeError::PayloadOverrun {
+                declared_len: payload_len,
+                remaining,
+            });
+        }
+        let payload = BytesMut::from(&data[offset..offset + payload_len]);
+        offset += payload_len + PREVIOUS_TAG_SIZE_LEN;
+
+        let base_timestamp = *base_timestamp.get_or_insert(timestamp);
+        let adjusted_timestamp = timestamp.wrapping_sub(base_timestamp);
+
+        match type_id {
+            msg_type_id::AUDIO => messages.push(MessageTypes::AudioData {
+                data: payload,
+                timestamp: adjusted_timestamp,
+            }),
+            msg_type_id::VIDEO => messages.push(MessageTypes::VideoData {
+                data: payload,
+                timestamp: adjusted_timestamp,
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sub_message(type_id: u8, timestamp: u32, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SUB_MESSAGE_HEADER_LEN + payload.len() + PREVIOUS_TAG_SIZE_LEN);
+        bytes.push(type_id);
+        let len = payload.len();
+        bytes.push((len >> 16) as u8);
+        bytes.push((len >> 8) as u8);
+        bytes.push(len as u8);
+        bytes.push((timestamp >> 16) as u8);
+        bytes.push((timestamp >> 8) as u8);
+        bytes.push(timestamp as u8);
+        bytes.push((timestamp >> 24) as u8);
+        bytes.extend_from_slice(&[0, 0, 0]); // stream id, always 0 in practice
+        bytes.extend_from_slice(payload);
+        let previous_tag_size = (SUB_MESSAGE_HEADER_LEN + len) as u32;
+        bytes.extend_from_slice(&previous_tag_size.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn decode_aggregate_splits_sub_messages_with_base_relative_timestamps() {
+        let mut data = sub_message(msg_type_id::AUDIO, 1000, &[1, 2, 3]);
+        data.extend(sub_message(msg_type_id::VIDEO, 1040, &[4, 5]));
+        let messages = decode_aggregate(&BytesMut::from(&data[..])).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        match &messages[0] {
+            MessageTypes::AudioData { data, timestamp } => {
+                assert_eq!(&data[..], &[1, 2, 3]);
+                assert_eq!(*timestamp, 0);
+            }
+            _ => panic!("expected AudioData"),
+        }
+        match &messages[1] {
+            MessageTypes::VideoData { data, timestamp } => {
+                assert_eq!(&data[..], &[4, 5]);
+                assert_eq!(*timestamp, 40);
+            }
+            _ => panic!("expected VideoData"),
+        }
+    }
+
+    #[test]
+    fn decode_aggregate_rejects_a_truncated_header() {
+        // Fewer than SUB_MESSAGE_HEADER_LEN bytes left for the next sub-message header.
+        let data = vec![0u8; SUB_MESSAGE_HEADER_LEN - 1];
+        let err = match decode_aggregate(&BytesMut::from(&data[..])) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        assert!(matches!(err, AggregateDecodeError::TruncatedHeader));
+    }
+
+    #[test]
+    fn decode_aggregate_rejects_a_payload_that_overruns_the_buffer() {
+        // Header declares a 100-byte payload, but only 3 bytes actually follow.
+        let mut data = sub_message(msg_type_id::AUDIO, 0, &[1, 2, 3]);
+        data[1] = 0;
+        data[2] = 0;
+        data[3] = 100;
+        let err = match decode_aggregate(&BytesMut::from(&data[..])) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an error"),
+        };
+        match err {
+            AggregateDecodeError::PayloadOverrun {
+                declared_len,
+                remaining,
+            } => {
+                assert_eq!(declared_len, 100);
+                assert_eq!(remaining, 3 + PREVIOUS_TAG_SIZE_LEN);
+            }
+            _ => panic!("expected PayloadOverrun"),
+        }
+    }
 }
\ No newline at end of file
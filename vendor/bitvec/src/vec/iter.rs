@@ -0,0 +1,169 @@
+//! Owning iteration over `BitVec`.
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	iter::FusedIterator,
+	ops::Range,
+};
+
+impl<O, T> IntoIterator for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Item = bool;
+	type IntoIter = IntoIter<O, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		let range = 0 .. self.len();
+		Self::IntoIter { buf: self, range }
+	}
+}
+
+/// An owning iterator over the bits of a `BitVec`.
+///
+/// This keeps the source `BitVec`'s allocation alive for the duration of iteration (so it
+/// drops correctly once exhausted), and tracks the still-outstanding bits as a `Range<usize>`
+/// rather than eagerly removing them, so that [`as_bitslice`] can cheaply view what remains.
+/// Mirrors [`BitBox`]'s owning iterator.
+///
+/// [`BitBox`]: crate::boxed::BitBox
+/// [`as_bitslice`]: Self::as_bitslice
+pub struct IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	buf: BitVec<O, T>,
+	range: Range<usize>,
+}
+
+impl<O, T> IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Views the bits that have not yet been yielded by the iterator.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.buf[self.range.clone()]
+	}
+
+	/// Mutably views the bits that have not yet been yielded by the iterator.
+	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<O, T> {
+		&mut self.buf[self.range.clone()]
+	}
+}
+
+impl<O, T> Iterator for IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Item = bool;
+
+	fn next(&mut self) -> Option<bool> {
+		let idx = self.range.next()?;
+		self.buf.get(idx)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.range.len();
+		(len, Some(len))
+	}
+}
+
+impl<O, T> DoubleEndedIterator for IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn next_back(&mut self) -> Option<bool> {
+		let idx = self.range.next_back()?;
+		self.buf.get(idx)
+	}
+}
+
+impl<O, T> ExactSizeIterator for IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn len(&self) -> usize {
+		self.range.len()
+	}
+}
+
+impl<O, T> FusedIterator for IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+impl<O, T> Debug for IntoIter<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_tuple("IntoIter").field(&self.as_bitslice()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Lsb0;
+
+	fn bv(bits: &[bool]) -> BitVec<Lsb0, u8> {
+		let mut v = BitVec::<Lsb0, u8>::with_capacity(bits.len());
+		for &bit in bits {
+			v.push(bit);
+		}
+		v
+	}
+
+	#[test]
+	fn into_iter_yields_bits_in_order_and_reports_exact_len() {
+		let v = bv(&[true, false, true]);
+		let mut iter = v.into_iter();
+		assert_eq!(iter.len(), 3);
+		assert_eq!(iter.next(), Some(true));
+		assert_eq!(iter.next(), Some(false));
+		assert_eq!(iter.len(), 1);
+		assert_eq!(iter.next(), Some(true));
+		assert_eq!(iter.next(), None);
+	}
+
+	#[test]
+	fn into_iter_supports_double_ended_iteration() {
+		let v = bv(&[true, false, true, false]);
+		let mut iter = v.into_iter();
+		assert_eq!(iter.next(), Some(true));
+		assert_eq!(iter.next_back(), Some(false));
+		assert_eq!(iter.next_back(), Some(true));
+		assert_eq!(iter.next(), Some(false));
+		assert_eq!(iter.next(), None);
+		assert_eq!(iter.next_back(), None);
+	}
+
+	#[test]
+	fn as_bitslice_views_only_the_not_yet_yielded_bits() {
+		let v = bv(&[true, false, true, true]);
+		let mut iter = v.into_iter();
+		iter.next();
+		assert_eq!(iter.as_bitslice().len(), 3);
+		assert_eq!(iter.as_bitslice()[0], false);
+	}
+}
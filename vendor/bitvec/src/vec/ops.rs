@@ -0,0 +1,169 @@
+//! Owned bitwise-operator trait implementations.
+//!
+//! `BitSlice` and `BitArray` already have full `BitAnd`/`BitOr`/`BitXor`/`Not` impls; `BitVec`
+//! only inherited the in-place `*Assign` versions through `Deref`. These impls add the
+//! by-value operators, with the length-reconciliation rules documented on `combine_bits`.
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::{
+	cmp,
+	ops::{
+		BitAnd,
+		BitOr,
+		BitXor,
+		Not,
+	},
+};
+
+// Applies `op` bitwise, pairwise, over `this` and `rhs`. The result keeps `this`'s length.
+// Bits in the overlapping prefix (`0 .. min(this.len(), rhs.len())`) become `op(this[i],
+// rhs[i])`. Bits in `this` beyond `rhs`'s length are treated as if `rhs` had a trailing `0`
+// there: when `zero_fill` is `true` (`BitAnd`), that forces them to `0`; when `false`
+// (`BitOr`/`BitXor`), `op(this[i], false) == this[i]`, so they are left untouched.
+fn combine_bits<O, T>(
+	mut this: BitVec<O, T>,
+	rhs: &BitSlice<O, T>,
+	op: impl Fn(bool, bool) -> bool,
+	zero_fill: bool,
+) -> BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let overlap = cmp::min(this.len(), rhs.len());
+	for idx in 0 .. this.len() {
+		let lhs = this.get(idx).unwrap_or(false);
+		let bit = if idx < overlap {
+			op(lhs, rhs.get(idx).unwrap_or(false))
+		}
+		else if zero_fill {
+			false
+		}
+		else {
+			lhs
+		};
+		this.set(idx, bit);
+	}
+	this
+}
+
+macro_rules! impl_bitop {
+	($trait:ident, $method:ident, $op:expr, $zero_fill:expr) => {
+		impl<O, T> $trait<&BitSlice<O, T>> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			type Output = Self;
+
+			fn $method(self, rhs: &BitSlice<O, T>) -> Self::Output {
+				combine_bits(self, rhs, $op, $zero_fill)
+			}
+		}
+
+		impl<O, T> $trait<&BitVec<O, T>> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			type Output = Self;
+
+			fn $method(self, rhs: &BitVec<O, T>) -> Self::Output {
+				combine_bits(self, rhs.as_bitslice(), $op, $zero_fill)
+			}
+		}
+
+		impl<O, T> $trait<BitVec<O, T>> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			type Output = Self;
+
+			fn $method(self, rhs: BitVec<O, T>) -> Self::Output {
+				combine_bits(self, rhs.as_bitslice(), $op, $zero_fill)
+			}
+		}
+	};
+}
+
+impl_bitop!(BitAnd, bitand, |a, b| a & b, true);
+impl_bitop!(BitOr, bitor, |a, b| a | b, false);
+impl_bitop!(BitXor, bitxor, |a, b| a ^ b, false);
+
+impl<O, T> Not for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Output = Self;
+
+	fn not(mut self) -> Self::Output {
+		for idx in 0 .. self.len() {
+			let bit = self.get(idx).unwrap_or(false);
+			self.set(idx, !bit);
+		}
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Lsb0;
+
+	fn bv(bits: &[bool]) -> BitVec<Lsb0, u8> {
+		let mut v = BitVec::<Lsb0, u8>::with_capacity(bits.len());
+		for &bit in bits {
+			v.push(bit);
+		}
+		v
+	}
+
+	#[test]
+	fn bitand_with_a_shorter_rhs_zero_fills_the_uncovered_tail() {
+		let a = bv(&[true, true, true, true]);
+		let b = bv(&[true, false]);
+		let c = a & b;
+		assert_eq!(c.len(), 4);
+		assert_eq!(
+			c.iter().by_val().collect::<Vec<_>>(),
+			vec![true, false, false, false]
+		);
+	}
+
+	#[test]
+	fn bitor_with_a_shorter_rhs_leaves_the_uncovered_tail_unchanged() {
+		let a = bv(&[false, false, true, true]);
+		let b = bv(&[true, false]);
+		let c = a | b;
+		assert_eq!(c.len(), 4);
+		assert_eq!(
+			c.iter().by_val().collect::<Vec<_>>(),
+			vec![true, false, true, true]
+		);
+	}
+
+	#[test]
+	fn bitxor_with_a_longer_rhs_ignores_the_excess_rhs_bits() {
+		let a = bv(&[true, false]);
+		let b = bv(&[true, true, true, true]);
+		let c = a ^ b;
+		// result takes the length of the left operand
+		assert_eq!(c.len(), 2);
+		assert_eq!(c.iter().by_val().collect::<Vec<_>>(), vec![false, true]);
+	}
+
+	#[test]
+	fn not_flips_every_bit() {
+		let a = bv(&[true, false, true]);
+		let c = !a;
+		assert_eq!(c.iter().by_val().collect::<Vec<_>>(), vec![false, true, false]);
+	}
+}
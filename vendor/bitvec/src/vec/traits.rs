@@ -26,6 +26,7 @@ use core::{
 		LowerHex,
 		Octal,
 		UpperHex,
+		Write as _,
 	},
 	hash::{
 		Hash,
@@ -182,6 +183,101 @@ where
 	}
 }
 
+// `BitSlice`'s blanket `PartialEq`/`PartialOrd` impls only fire for types that already know
+// how to compare against a `BitSlice`. Plain `bool` sequences don't, so give `BitVec` direct,
+// paired (both-directions) impls against them instead.
+macro_rules! impl_partial_eq {
+	($($rhs:ty),+ $(,)?) => { $(
+		impl<O, T> PartialEq<$rhs> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			fn eq(&self, other: &$rhs) -> bool {
+				self.len() == other.len()
+					&& self.iter().by_vals().zip(other.iter().copied()).all(|(a, b)| a == b)
+			}
+		}
+
+		impl<O, T> PartialEq<BitVec<O, T>> for $rhs
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			fn eq(&self, other: &BitVec<O, T>) -> bool {
+				other == self
+			}
+		}
+	)+ };
+}
+
+macro_rules! impl_partial_ord {
+	($($rhs:ty),+ $(,)?) => { $(
+		impl<O, T> PartialOrd<$rhs> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			fn partial_cmp(&self, other: &$rhs) -> Option<cmp::Ordering> {
+				Some(self.iter().by_vals().cmp(other.iter().copied()))
+			}
+		}
+
+		impl<O, T> PartialOrd<BitVec<O, T>> for $rhs
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			fn partial_cmp(&self, other: &BitVec<O, T>) -> Option<cmp::Ordering> {
+				other.partial_cmp(self).map(cmp::Ordering::reverse)
+			}
+		}
+	)+ };
+}
+
+impl_partial_eq!([bool], &[bool], Vec<bool>);
+impl_partial_ord!([bool], &[bool], Vec<bool>);
+
+impl<O, T, const N: usize> PartialEq<[bool; N]> for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn eq(&self, other: &[bool; N]) -> bool {
+		self == &other[..]
+	}
+}
+
+impl<O, T, const N: usize> PartialEq<BitVec<O, T>> for [bool; N]
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn eq(&self, other: &BitVec<O, T>) -> bool {
+		other == self
+	}
+}
+
+impl<O, T, const N: usize> PartialOrd<[bool; N]> for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn partial_cmp(&self, other: &[bool; N]) -> Option<cmp::Ordering> {
+		self.partial_cmp(&other[..])
+	}
+}
+
+impl<O, T, const N: usize> PartialOrd<BitVec<O, T>> for [bool; N]
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn partial_cmp(&self, other: &BitVec<O, T>) -> Option<cmp::Ordering> {
+		other.partial_cmp(self).map(cmp::Ordering::reverse)
+	}
+}
+
 impl<O, T> AsRef<BitSlice<O, T>> for BitVec<O, T>
 where
 	O: BitOrder,
@@ -289,12 +385,64 @@ where
 	}
 }
 
+// Renders `bits` as a sequence of `radix`-digits, each covering `bits_per_digit` bits of
+// the buffer. In alternate mode (`{:#b}`, `{:#o}`, `{:#x}`, `{:#X}`), digits are grouped
+// every `digits_per_group` digits with a
+// space, and every `LINE_GROUPS` groups the output wraps to a new line prefixed with the
+// bit offset of the first digit on that line (hex, zero-padded to seven digits, mirroring
+// the offset column of a byte-oriented hex dump).
+const LINE_GROUPS: usize = 4;
+
+fn fmt_grouped<O, T>(
+	bits: &BitSlice<O, T>,
+	fmt: &mut Formatter,
+	bits_per_digit: usize,
+	digits_per_group: usize,
+	radix: u32,
+	upper: bool,
+) -> fmt::Result
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let group_bits = bits_per_digit * digits_per_group;
+	let line_bits = group_bits * LINE_GROUPS;
+	let total = bits.len();
+	let mut i = 0usize;
+	while i < total {
+		if i % line_bits == 0 {
+			if i > 0 {
+				fmt.write_str("\n")?;
+			}
+			write!(fmt, "{:07x}: ", i)?;
+		}
+		else if i % group_bits == 0 {
+			fmt.write_str(" ")?;
+		}
+
+		let end = cmp::min(i + bits_per_digit, total);
+		let mut value = 0u32;
+		for bit in bits[i .. end].iter().by_vals() {
+			value = (value << 1) | u32::from(bit);
+		}
+		value <<= bits_per_digit - (end - i);
+
+		let digit = char::from_digit(value, radix).unwrap_or('?');
+		fmt.write_char(if upper { digit.to_ascii_uppercase() } else { digit })?;
+		i = end;
+	}
+	Ok(())
+}
+
 impl<O, T> Binary for BitVec<O, T>
 where
 	O: BitOrder,
 	T: BitStore,
 {
 	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		if fmt.alternate() {
+			return fmt_grouped(self.as_bitslice(), fmt, 1, 8, 2, false);
+		}
 		Binary::fmt(self.as_bitslice(), fmt)
 	}
 }
@@ -305,6 +453,9 @@ where
 	T: BitStore,
 {
 	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		if fmt.alternate() {
+			return fmt_grouped(self.as_bitslice(), fmt, 4, 2, 16, false);
+		}
 		LowerHex::fmt(self.as_bitslice(), fmt)
 	}
 }
@@ -315,6 +466,9 @@ where
 	T: BitStore,
 {
 	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		if fmt.alternate() {
+			return fmt_grouped(self.as_bitslice(), fmt, 3, 3, 8, false);
+		}
 		Octal::fmt(self.as_bitslice(), fmt)
 	}
 }
@@ -325,6 +479,9 @@ where
 	T: BitStore,
 {
 	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		if fmt.alternate() {
+			return fmt_grouped(self.as_bitslice(), fmt, 4, 2, 16, true);
+		}
 		UpperHex::fmt(self.as_bitslice(), fmt)
 	}
 }
@@ -361,3 +518,89 @@ where
 	T: BitStore,
 {
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Lsb0;
+
+	fn bv(bits: &[bool]) -> BitVec<Lsb0, u8> {
+		let mut v = BitVec::<Lsb0, u8>::with_capacity(bits.len());
+		for &bit in bits {
+			v.push(bit);
+		}
+		v
+	}
+
+	#[test]
+	fn fmt_grouped_wraps_to_a_new_offset_prefixed_line_at_the_line_boundary() {
+		// Binary: bits_per_digit = 1, digits_per_group = 8, LINE_GROUPS = 4, so a line holds
+		// 32 bits; 40 bits should wrap to exactly one continuation line.
+		let v = bv(&[true; 40]);
+		let rendered = format!("{:#b}", v);
+		let lines: Vec<&str> = rendered.lines().collect();
+		assert_eq!(lines.len(), 2);
+		assert!(lines[0].starts_with("0000000: "));
+		assert!(lines[1].starts_with("0000020: "));
+	}
+
+	#[test]
+	fn fmt_grouped_pads_a_partial_final_digit() {
+		// LowerHex: bits_per_digit = 4. 6 bits makes one full nibble plus 2 leftover bits,
+		// which must be left-shifted into the high bits of the final digit rather than
+		// truncated or right-aligned.
+		let v = bv(&[true, false, true, true, true, true]);
+		// first nibble: 1011 = 0xb; second (partial) nibble: bits `11` shifted up = 1100 = 0xc
+		assert_eq!(format!("{:#x}", v), "0000000: bc");
+	}
+
+	#[test]
+	fn fmt_grouped_upper_hex_uppercases_digits() {
+		let v = bv(&[true, true, true, true]);
+		assert_eq!(format!("{:#X}", v), "0000000: F");
+	}
+
+	#[test]
+	fn partial_eq_and_partial_ord_are_symmetric_against_a_bool_slice() {
+		let v = bv(&[true, false, true]);
+		let slice: &[bool] = &[true, false, true];
+		assert!(v == *slice);
+		assert!(*slice == v);
+		assert_eq!(
+			v.partial_cmp(slice),
+			slice.partial_cmp(&v).map(cmp::Ordering::reverse)
+		);
+
+		let other: &[bool] = &[true, true];
+		assert_ne!(v, *other);
+		assert_ne!(*other, v);
+	}
+
+	#[test]
+	fn partial_eq_and_partial_ord_are_symmetric_against_a_bool_vec() {
+		let v = bv(&[false, true]);
+		let other = alloc::vec![false, true];
+		assert!(v == other);
+		assert!(other == v);
+		assert_eq!(
+			v.partial_cmp(&other),
+			other.partial_cmp(&v).map(cmp::Ordering::reverse)
+		);
+	}
+
+	#[test]
+	fn partial_eq_and_partial_ord_are_symmetric_against_a_bool_array() {
+		let v = bv(&[true, true, false]);
+		let array = [true, true, false];
+		assert!(v == array);
+		assert!(array == v);
+		assert_eq!(
+			v.partial_cmp(&array),
+			array.partial_cmp(&v).map(cmp::Ordering::reverse)
+		);
+
+		let different = [true, false, false];
+		assert_ne!(v, different);
+		assert_ne!(different, v);
+	}
+}
@@ -126,6 +126,22 @@
 //!   }
 //!   ```
 //!
+//! - with [`WriteMode::SupportCapture`](crate::WriteMode::SupportCapture), stdout/stderr output
+//!   goes through the `print!`/`eprintln!` macros instead of writing to the streams directly, so
+//!   it plays along with `cargo test`'s output capture. Use this write mode in tests that rely
+//!   on seeing their own log output; it's slower than `Direct` and not meant for production use.
+//!
+//!   ```rust
+//!   # use flexi_logger::{WriteMode, Logger};
+//!   fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!       let _logger = Logger::try_with_str("info")?
+//!          .write_mode(WriteMode::SupportCapture)
+//!          .start()?;
+//!       // ... do all your work ...
+//!       Ok(())
+//!   }
+//!   ```
+//!
 //! **Note** that with all write modes
 //! except [`WriteMode::Direct`](crate::WriteMode::Direct) (which is the default)
 //! you should keep the [`LoggerHandle`](crate::LoggerHandle) alive
@@ -1,8 +1,37 @@
 use crate::FlexiLoggerError;
-use chrono::Local;
+use chrono::{Local, Utc};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+// Appends `component` to `filename`, inserting a `_` separator first, but only if `filename`
+// already has content -- so an empty basename, discriminant, or timestamp never leaves a
+// leading or doubled underscore behind.
+fn push_raw(filename: &mut String, component: &str) {
+    if component.is_empty() {
+        return;
+    }
+    if !filename.is_empty() {
+        filename.push('_');
+    }
+    filename.push_str(component);
+}
+
+// Like `push_raw`, but `component` (an infix like `_rCURRENT`) already carries its own leading
+// `_` as a built-in separator; that leading `_` is stripped when it would otherwise be the very
+// first character of the filename.
+fn push_prefixed(filename: &mut String, component: &str) {
+    if component.is_empty() {
+        return;
+    }
+    if filename.is_empty() {
+        filename.push_str(component.strip_prefix('_').unwrap_or(component));
+    } else {
+        filename.push_str(component);
+    }
+}
+
 /// Builder object for specifying the name and path of the log output file.
 ///
 /// ```rust
@@ -22,6 +51,8 @@ pub struct FileSpec {
     pub(crate) basename: String,
     pub(crate) o_discriminant: Option<String>,
     timestamp_cfg: TimestampCfg,
+    timestamp_format: String,
+    use_utc: bool,
     pub(crate) o_suffix: Option<String>,
 }
 impl Default for FileSpec {
@@ -35,6 +66,8 @@ impl Default for FileSpec {
             basename: Self::default_basename(),
             o_discriminant: None,
             timestamp_cfg: TimestampCfg::Default,
+            timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_owned(),
+            use_utc: false,
             o_suffix: Some(String::from("log")),
         }
     }
@@ -67,6 +100,8 @@ impl FileSpec {
                 o_discriminant: None,
                 o_suffix: p.extension().map(|s| s.to_string_lossy().to_string()),
                 timestamp_cfg: TimestampCfg::No,
+                timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_owned(),
+                use_utc: false,
             })
         }
     }
@@ -155,6 +190,27 @@ impl FileSpec {
         self
     }
 
+    /// Sets the chrono strftime pattern used to render the timestamp, instead of the default
+    /// `"%Y-%m-%d_%H-%M-%S"`.
+    ///
+    /// Has no effect if the timestamp is suppressed, see [`Self::suppress_timestamp`].
+    #[must_use]
+    pub fn timestamp_format<S: Into<String>>(mut self, timestamp_format: S) -> Self {
+        self.timestamp_format = timestamp_format.into();
+        self
+    }
+
+    /// Specifies if the timestamp in the log file name should be rendered in UTC rather than
+    /// in local time (the default).
+    ///
+    /// This matters for servers whose log filenames must sort and correlate identically across
+    /// machines in different timezones.
+    #[must_use]
+    pub fn use_utc(mut self, use_utc: bool) -> Self {
+        self.use_utc = use_utc;
+        self
+    }
+
     // If no decison was done yet, decide now whether to include a timestamp
     // into the names of the log files.
     pub(crate) fn if_default_use_timestamp(&mut self, use_timestamp: bool) {
@@ -173,19 +229,22 @@ impl FileSpec {
 
     // <directory>/<basename>_<discr>_<timestamp><infix>.<suffix>
     pub(crate) fn as_pathbuf(&self, o_infix: Option<&str>) -> PathBuf {
-        let mut filename = self.basename.clone();
-        filename.reserve(50);
+        let mut filename = String::new();
+        filename.reserve(self.basename.len() + 50);
 
+        push_raw(&mut filename, &self.basename);
         if let Some(discriminant) = &self.o_discriminant {
-            filename.push('_');
-            filename.push_str(discriminant);
+            push_raw(&mut filename, discriminant);
         }
-        if let Some(timestamp) = &self.timestamp_cfg.get_timestamp() {
-            filename.push_str(timestamp);
+        if let Some(timestamp) = &self
+            .timestamp_cfg
+            .get_timestamp(&self.timestamp_format, self.use_utc)
+        {
+            push_raw(&mut filename, timestamp);
         }
         if let Some(infix) = o_infix {
-            filename.push_str(infix);
-        };
+            push_prefixed(&mut filename, infix);
+        }
         if let Some(suffix) = &self.o_suffix {
             filename.push('.');
             filename.push_str(suffix);
@@ -198,19 +257,22 @@ impl FileSpec {
 
     // <directory>/<basename>_<discr>_<timestamp><infix>.<suffix>
     pub(crate) fn as_glob_pattern(&self, o_infix: Option<&str>, o_suffix: Option<&str>) -> String {
-        let mut filename = self.basename.clone();
-        filename.reserve(50);
+        let mut filename = String::new();
+        filename.reserve(self.basename.len() + 50);
 
+        push_raw(&mut filename, &self.basename);
         if let Some(discriminant) = &self.o_discriminant {
-            filename.push('_');
-            filename.push_str(&discriminant);
+            push_raw(&mut filename, discriminant);
         }
-        if let Some(timestamp) = &self.timestamp_cfg.get_timestamp() {
-            filename.push_str(&timestamp);
+        if let Some(timestamp) = &self
+            .timestamp_cfg
+            .get_timestamp(&self.timestamp_format, self.use_utc)
+        {
+            push_raw(&mut filename, timestamp);
         }
         if let Some(infix) = o_infix {
-            filename.push_str(infix);
-        };
+            push_prefixed(&mut filename, infix);
+        }
         match o_suffix {
             Some(s) => {
                 filename.push('.');
@@ -228,6 +290,87 @@ impl FileSpec {
         p_path.push(filename);
         p_path.to_str().unwrap(/* can hardly fail*/).to_string()
     }
+
+    // A sibling of `as_pathbuf(o_infix)`, in the same `directory`, with a randomized
+    // `.<8-hex-chars>.tmp` suffix appended to the final file name.
+    //
+    // Staying in the same directory is essential: callers stage content here and then
+    // `std::fs::rename` it onto `as_pathbuf(o_infix)`, and `rename` is only atomic within a
+    // single filesystem -- a temp file on another mount could leave the real path half-written
+    // if the process died mid-copy.
+    pub(crate) fn tmp_pathbuf(&self, o_infix: Option<&str>) -> PathBuf {
+        tmp_sibling(&self.as_pathbuf(o_infix))
+    }
+
+    // Writes `contents` to `tmp_pathbuf(o_infix)` and renames it onto `as_pathbuf(o_infix)`, so
+    // a reader of the final path never observes a half-written file: the rename is atomic on the
+    // same filesystem, which is where `tmp_pathbuf` always places the temp file.
+    //
+    // If the rename still fails with `ErrorKind::CrossesDevices` (e.g. the directory was
+    // remounted between the two calls), falls back to writing `contents` directly to the final
+    // path, trading the atomicity guarantee for still getting the content written.
+    pub(crate) fn write_atomically(&self, o_infix: Option<&str>, contents: &[u8]) -> std::io::Result<()> {
+        let target = self.as_pathbuf(o_infix);
+        let tmp = self.tmp_pathbuf(o_infix);
+        std::fs::write(&tmp, contents)?;
+        match std::fs::rename(&tmp, &target) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                let _ = std::fs::remove_file(&tmp);
+                std::fs::write(&target, contents)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// A sibling of `target`, in the same directory, with a randomized `.<8-hex-chars>.tmp` suffix
+// appended to the final file name. Free-standing (not a `FileSpec` method) so callers that only
+// have a `&Path` -- e.g. the rotation/compression/symlink code in `writers::file_log_writer` --
+// can stage their own atomic writes with the same convention `FileSpec::tmp_pathbuf` uses.
+pub(crate) fn tmp_sibling(target: &Path) -> PathBuf {
+    let mut file_name = target
+        .file_name()
+        .unwrap(/*cannot fail for any path a caller would stage a sibling for*/)
+        .to_string_lossy()
+        .to_string();
+    file_name.push('.');
+    file_name.push_str(&random_hex_suffix());
+    file_name.push_str(".tmp");
+    target.with_file_name(file_name)
+}
+
+// Renames `tmp` onto `target`, the same atomic-replace `write_atomically` relies on, for callers
+// that staged their own `tmp_sibling` (e.g. by streaming into it) rather than holding the
+// content as an in-memory buffer. Falls back to copying `tmp`'s bytes onto `target` and removing
+// `tmp` if the rename fails with `ErrorKind::CrossesDevices`, trading the atomicity guarantee for
+// still getting the content written.
+pub(crate) fn rename_or_copy(tmp: &Path, target: &Path) -> std::io::Result<()> {
+    match std::fs::rename(tmp, target) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            std::fs::copy(tmp, target)?;
+            std::fs::remove_file(tmp)?;
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// A short, unique-enough-for-a-filename hex suffix: nanosecond timestamp, process id, and a
+// per-process counter all folded together, so two temp files created in the same process in the
+// same nanosecond (or across processes sharing a pid, after a restart) still don't collide.
+fn random_hex_suffix() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let pid = std::process::id();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{:08x}", nanos ^ pid ^ count)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -237,11 +380,13 @@ enum TimestampCfg {
     No,
 }
 impl TimestampCfg {
-    fn get_timestamp(&self) -> Option<String> {
+    fn get_timestamp(&self, format: &str, use_utc: bool) -> Option<String> {
         match self {
-            Self::Default | Self::Yes => {
-                Some(Local::now().format("_%Y-%m-%d_%H-%M-%S").to_string())
-            }
+            Self::Default | Self::Yes => Some(if use_utc {
+                Utc::now().format(format).to_string()
+            } else {
+                Local::now().format(format).to_string()
+            }),
             Self::No => None,
         }
     }
@@ -419,4 +564,101 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_empty_basename_with_infix() {
+        {
+            // empty basename, no discriminant, no timestamp: the infix's own leading
+            // underscore must not be duplicated into a leading underscore on the filename
+            let path = FileSpec::try_from("/a/b/c/d_foo_bar.log")
+                .unwrap()
+                .o_basename(Option::<String>::None)
+                .basename("")
+                .o_suffix(Some("log"))
+                .as_pathbuf(Some("_rCURRENT"));
+            assert_eq!(
+                path.file_name().unwrap().to_str().unwrap(),
+                "rCURRENT.log"
+            );
+        }
+        {
+            // empty basename, with discriminant and infix: still no leading underscore, and
+            // exactly one underscore between the two non-empty components
+            let path = FileSpec::try_from("/a/b/c/d_foo_bar.log")
+                .unwrap()
+                .basename("")
+                .o_suffix(Some("log"))
+                .o_discriminant(Some("1234"))
+                .as_pathbuf(Some("_rCURRENT"));
+            assert_eq!(
+                path.file_name().unwrap().to_str().unwrap(),
+                "1234_rCURRENT.log"
+            );
+        }
+    }
+
+    #[test]
+    fn test_basename_with_infix_unchanged() {
+        // with a non-empty basename, the infix still appends with exactly the separator it
+        // always carried -- no behavior change for existing users
+        let path = FileSpec::try_from("/a/b/c/d_foo_bar.log")
+            .unwrap()
+            .o_suffix(Some("log"))
+            .o_discriminant(Some("1234"))
+            .as_pathbuf(Some("_rCURRENT"));
+        assert_eq!(
+            path.file_name().unwrap().to_str().unwrap(),
+            "d_foo_bar_1234_rCURRENT.log"
+        );
+    }
+
+    #[test]
+    fn test_tmp_pathbuf_is_sibling_with_tmp_suffix() {
+        let fs = FileSpec::default()
+            .directory(std::env::temp_dir())
+            .basename("flexi_logger_test_tmp_pathbuf")
+            .suppress_timestamp();
+        let target = fs.as_pathbuf(None);
+        let tmp = fs.tmp_pathbuf(None);
+
+        assert_eq!(tmp.parent(), target.parent());
+        let tmp_name = tmp.file_name().unwrap().to_str().unwrap().to_string();
+        assert!(tmp_name.starts_with(target.file_name().unwrap().to_str().unwrap()));
+        assert!(tmp_name.ends_with(".tmp"));
+        assert_ne!(tmp, target);
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let fs = FileSpec::default()
+            .directory(std::env::temp_dir())
+            .basename("flexi_logger_test_write_atomically")
+            .suppress_timestamp();
+        let target = fs.as_pathbuf(None);
+        let _ = std::fs::remove_file(&target);
+
+        fs.write_atomically(None, b"hello").unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"hello");
+
+        std::fs::remove_file(&target).unwrap();
+    }
+
+    #[test]
+    fn test_rename_or_copy_stages_via_tmp_sibling() {
+        use super::{rename_or_copy, tmp_sibling};
+
+        let target = std::env::temp_dir().join("flexi_logger_test_rename_or_copy.log");
+        let _ = std::fs::remove_file(&target);
+
+        let tmp = tmp_sibling(&target);
+        assert_eq!(tmp.parent(), target.parent());
+        assert!(tmp.file_name().unwrap().to_str().unwrap().ends_with(".tmp"));
+
+        std::fs::write(&tmp, b"staged").unwrap();
+        rename_or_copy(&tmp, &target).unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"staged");
+        assert!(!tmp.exists());
+
+        std::fs::remove_file(&target).unwrap();
+    }
 }
@@ -0,0 +1,302 @@
+//! Infrastructure for intercepting and transforming log lines before they reach the
+//! configured writer(s), via [`Logger::filter`](crate::Logger::filter).
+//!
+//! flexi_logger ships one filter out of the box, [`Dedup`], which collapses runs of
+//! identical consecutive messages into a single line plus a repeat-count summary.
+
+use crate::deferred_now::DeferredNow;
+use log::Record;
+use std::io;
+use std::sync::Mutex;
+
+/// What a [`LogLineFilter`] writes a (possibly rewritten) record through to, once it has
+/// decided the record should actually be emitted.
+///
+/// Implemented by `PrimaryWriter`, so filters don't need to know whether they're ultimately
+/// writing to stdout, stderr, a file, or several of these at once.
+pub trait LogLineWriter {
+    /// Writes the record.
+    ///
+    /// # Errors
+    ///
+    /// Propagates the underlying writer's I/O errors.
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> io::Result<()>;
+}
+
+/// A stage that runs ahead of the configured writer(s), able to rewrite, suppress, or pass
+/// records through unchanged. Installed with [`Logger::filter`](crate::Logger::filter).
+///
+/// # Example
+///
+/// Collapse consecutive duplicate messages with [`Dedup`]:
+///
+/// ```rust
+/// # use flexi_logger::{Dedup, DedupStrategy, Logger};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// Logger::try_with_str("info")?
+///     .filter(Box::new(Dedup::new(DedupStrategy::Exact)))
+///     .start()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait LogLineFilter {
+    /// Inspects (and may rewrite or suppress) a record before it reaches `log_line_writer`.
+    ///
+    /// # Errors
+    ///
+    /// Propagates whatever `log_line_writer` returns.
+    fn write(
+        &self,
+        now: &mut DeferredNow,
+        record: &Record,
+        log_line_writer: &dyn LogLineWriter,
+    ) -> io::Result<()>;
+
+    /// Called whenever the logger is flushed or shut down, so a filter that's holding output
+    /// back (like [`Dedup`]) gets a chance to emit whatever it's still suppressing.
+    ///
+    /// Default implementation does nothing, which is correct for filters that never withhold
+    /// output past their own `write` call.
+    ///
+    /// # Errors
+    ///
+    /// Propagates whatever `log_line_writer` returns.
+    fn flush_pending(
+        &self,
+        _now: &mut DeferredNow,
+        _log_line_writer: &dyn LogLineWriter,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// How [`Dedup`] decides that two consecutive records are "the same".
+#[derive(Debug, Clone, Copy)]
+pub enum DedupStrategy {
+    /// Two records are duplicates if their fully formatted output is byte-for-byte identical.
+    Exact,
+}
+
+struct PendingRun {
+    formatted: Vec<u8>,
+    count: usize,
+}
+
+/// A [`LogLineFilter`] that collapses a run of consecutive, identical messages into the first
+/// occurrence followed by a single `"... last message repeated N times ..."` summary, instead
+/// of writing every repetition.
+///
+/// Install it with [`Logger::filter`](crate::Logger::filter) (or the
+/// [`Logger::dedup`](crate::Logger::dedup) shorthand). The pending summary is flushed as soon
+/// as a *different* message arrives, once it reaches `max_suppressed` repeats (if one was set
+/// via [`Dedup::with_max_suppressed`]), and whenever [`LogLineFilter::flush_pending`] is
+/// called -- which `LoggerHandle` now does from its own `flush()`/`shutdown()`/`Drop` and from
+/// the periodic flusher thread (see [`Logger::write_mode`](crate::Logger::write_mode)), so a
+/// suppression that's stuck at some count still surfaces within that flush interval instead of
+/// only when new, different log traffic arrives.
+pub struct Dedup {
+    strategy: DedupStrategy,
+    max_suppressed: Option<usize>,
+    state: Mutex<Option<PendingRun>>,
+}
+
+impl Dedup {
+    /// Creates a `Dedup` filter with no cap on how many repeats are suppressed before the
+    /// summary is forced out early; use [`Dedup::with_max_suppressed`] to add one.
+    #[must_use]
+    pub fn new(strategy: DedupStrategy) -> Self {
+        Self {
+            strategy,
+            max_suppressed: None,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Forces the pending summary out once this many repeats have been suppressed, rather than
+    /// only when a different message arrives or a flush happens.
+    #[must_use]
+    pub fn with_max_suppressed(mut self, max_suppressed: usize) -> Self {
+        self.max_suppressed = Some(max_suppressed);
+        self
+    }
+
+    fn is_duplicate(&self, pending: &PendingRun, formatted: &[u8]) -> bool {
+        match self.strategy {
+            DedupStrategy::Exact => pending.formatted == formatted,
+        }
+    }
+
+    fn summary_text(count: usize) -> String {
+        format!("... last message repeated {count} times ...")
+    }
+
+    // Writes out the pending summary, if there is one and it represents more than one
+    // occurrence (a single, non-repeated occurrence was already written as a normal line by
+    // `write`, so there's nothing extra to say about it).
+    fn flush_locked(
+        &self,
+        now: &mut DeferredNow,
+        log_line_writer: &dyn LogLineWriter,
+        state: &mut Option<PendingRun>,
+    ) -> io::Result<()> {
+        if let Some(pending) = state.take() {
+            if pending.count > 1 {
+                let summary = Self::summary_text(pending.count - 1);
+                let record = Record::builder()
+                    .args(format_args!("{summary}"))
+                    .level(log::Level::Info)
+                    .target("flexi_logger::dedup")
+                    .build();
+                log_line_writer.write(now, &record)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LogLineFilter for Dedup {
+    fn write(
+        &self,
+        now: &mut DeferredNow,
+        record: &Record,
+        log_line_writer: &dyn LogLineWriter,
+    ) -> io::Result<()> {
+        let mut formatted = Vec::<u8>::with_capacity(200);
+        crate::formats::default_format(&mut formatted, now, record)?;
+
+        let mut state = self.state.lock().unwrap(/* ok, not expected to be poisoned */);
+        if let Some(pending) = state.as_mut() {
+            if self.is_duplicate(pending, &formatted) {
+                pending.count += 1;
+                if self.max_suppressed == Some(pending.count - 1) {
+                    self.flush_locked(now, log_line_writer, &mut state)?;
+                }
+                return Ok(());
+            }
+            self.flush_locked(now, log_line_writer, &mut state)?;
+        }
+
+        log_line_writer.write(now, record)?;
+        *state = Some(PendingRun { formatted, count: 1 });
+        Ok(())
+    }
+
+    fn flush_pending(
+        &self,
+        now: &mut DeferredNow,
+        log_line_writer: &dyn LogLineWriter,
+    ) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap(/* ok, not expected to be poisoned */);
+        self.flush_locked(now, log_line_writer, &mut state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records each formatted line it's handed, rather than writing anywhere, so tests can
+    // assert on exactly what a `Dedup` passed through.
+    struct RecordingWriter {
+        lines: Mutex<Vec<String>>,
+    }
+
+    impl RecordingWriter {
+        fn new() -> Self {
+            Self {
+                lines: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn lines(&self) -> Vec<String> {
+            self.lines.lock().unwrap(/* ok */).clone()
+        }
+    }
+
+    impl LogLineWriter for RecordingWriter {
+        fn write(&self, now: &mut DeferredNow, record: &Record) -> io::Result<()> {
+            let mut formatted = Vec::<u8>::with_capacity(200);
+            crate::formats::default_format(&mut formatted, now, record)?;
+            self.lines
+                .lock()
+                .unwrap(/* ok */)
+                .push(String::from_utf8_lossy(&formatted).into_owned());
+            Ok(())
+        }
+    }
+
+    fn write_message(dedup: &Dedup, writer: &RecordingWriter, message: &str) {
+        let record = Record::builder()
+            .args(format_args!("{message}"))
+            .level(log::Level::Info)
+            .target("test")
+            .build();
+        dedup
+            .write(&mut DeferredNow::new(), &record, writer)
+            .unwrap();
+    }
+
+    #[test]
+    fn collapses_a_run_of_identical_messages_until_a_different_one_arrives() {
+        let dedup = Dedup::new(DedupStrategy::Exact);
+        let writer = RecordingWriter::new();
+
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "world");
+
+        let lines = writer.lines();
+        // "hello" is written once up front, then the repeats are suppressed until "world"
+        // arrives and forces the pending run's summary out.
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("hello"));
+        assert!(lines[1].contains("repeated 2 times"));
+        assert!(lines[2].contains("world"));
+    }
+
+    #[test]
+    fn flushes_the_pending_summary_early_once_max_suppressed_is_reached() {
+        let dedup = Dedup::new(DedupStrategy::Exact).with_max_suppressed(2);
+        let writer = RecordingWriter::new();
+
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "hello");
+
+        // the 3rd "hello" is the 2nd repeat, hitting max_suppressed, so the summary is
+        // emitted immediately rather than waiting for a different message or an explicit flush.
+        let lines = writer.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("hello"));
+        assert!(lines[1].contains("repeated 2 times"));
+    }
+
+    #[test]
+    fn flush_pending_emits_a_suppressed_run_with_no_further_log_traffic() {
+        let dedup = Dedup::new(DedupStrategy::Exact);
+        let writer = RecordingWriter::new();
+
+        write_message(&dedup, &writer, "hello");
+        write_message(&dedup, &writer, "hello");
+        assert_eq!(writer.lines().len(), 1);
+
+        dedup.flush_pending(&mut DeferredNow::new(), &writer).unwrap();
+        let lines = writer.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains("repeated 1 times"));
+    }
+
+    #[test]
+    fn flush_pending_emits_nothing_for_a_single_unrepeated_message() {
+        let dedup = Dedup::new(DedupStrategy::Exact);
+        let writer = RecordingWriter::new();
+
+        write_message(&dedup, &writer, "hello");
+        dedup.flush_pending(&mut DeferredNow::new(), &writer).unwrap();
+
+        // a single occurrence was already written by `write`; there's no repeat count to
+        // report, so `flush_pending` must not emit a second line for it.
+        assert_eq!(writer.lines().len(), 1);
+    }
+}
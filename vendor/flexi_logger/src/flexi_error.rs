@@ -17,6 +17,18 @@ pub enum FlexiLoggerError {
     #[error("Log file cannot be written because the specified path is a directory")]
     OutputBadFile,
 
+    /// Reopening the log file failed.
+    ///
+    /// This can happen when `reopen()` is called, e.g. from a `SIGHUP` handler, to recover
+    /// from an external tool (like `logrotate`) having renamed or removed the active log file.
+    #[error("Reopening the log file failed")]
+    ReopenIo(std::io::Error),
+
+    /// Connecting to the systemd journal socket failed.
+    #[error("Connecting to the systemd journal socket failed")]
+    #[cfg(feature = "journal")]
+    JournalSocket(std::io::Error),
+
     /// Spawning the cleanup thread failed.
     ///
     /// This error can safely be avoided with `Logger::cleanup_in_background_thread(false)`.
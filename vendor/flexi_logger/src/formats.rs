@@ -0,0 +1,162 @@
+// Additional `FormatFunction`s beyond the plain-text `default_format` and friends (those live
+// alongside it in this module; only the JSON variants are added here).
+
+use crate::deferred_now::DeferredNow;
+use log::Record;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+
+// Static key-value pairs installed via `Logger::add_kv`, merged into every JSON-formatted
+// record's `fields` object. Plain `FormatFunction`s are bare `fn` pointers, so they can't
+// capture per-`Logger` state directly; `Logger::build` populates this once, analogous to how
+// it calls `set_palette` for the plain-text formatters' coloring.
+static STATIC_KV: OnceLock<Mutex<Vec<(String, String)>>> = OnceLock::new();
+
+// Replaces the static key-value pairs merged into JSON output. Called once from `Logger::build`.
+pub(crate) fn set_static_kv(fields: Vec<(String, String)>) {
+    *STATIC_KV
+        .get_or_init(|| Mutex::new(Vec::new()))
+        .lock()
+        .unwrap(/* ok, not expected to be poisoned */) = fields;
+}
+
+// Appends `s` as a JSON string literal, escaping `"`, `\`, and control characters.
+fn write_json_escaped(out: &mut dyn Write, s: &str) -> std::io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    out.write_all(b"\"")
+}
+
+// `log::kv::Value` only promises a stable `Display`/`Debug` rendering across versions, not a
+// typed visitor, so a kv value that renders as a bare number or boolean is emitted unquoted;
+// everything else is emitted as an escaped JSON string.
+fn write_json_value(out: &mut dyn Write, rendered: &str) -> std::io::Result<()> {
+    if rendered == "true" || rendered == "false" || rendered.parse::<f64>().is_ok() {
+        out.write_all(rendered.as_bytes())
+    } else {
+        write_json_escaped(out, rendered)
+    }
+}
+
+struct FieldsVisitor<'a> {
+    out: &'a mut dyn Write,
+    count: usize,
+    err: std::io::Result<()>,
+}
+impl<'a, 'kvs> log::kv::Visitor<'kvs> for FieldsVisitor<'a> {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        let result = (|| -> std::io::Result<()> {
+            if self.count > 0 {
+                self.out.write_all(b",")?;
+            }
+            write_json_escaped(self.out, key.as_str())?;
+            self.out.write_all(b":")?;
+            write_json_value(self.out, &value.to_string())
+        })();
+        self.count += 1;
+        if let Err(e) = result {
+            self.err = Err(e);
+        }
+        Ok(())
+    }
+}
+
+// Writes the `"fields":{...}` object shared by `json_format` and `json_compact_format`: the
+// static pairs installed via `Logger::add_kv`, followed by the record's own `log::kv` pairs.
+fn write_fields(w: &mut dyn Write, record: &Record) -> std::io::Result<()> {
+    write!(w, "{{")?;
+    let mut count = 0;
+    if let Some(static_kv) = STATIC_KV.get() {
+        for (key, value) in &*static_kv.lock().unwrap(/* ok, not expected to be poisoned */) {
+            if count > 0 {
+                w.write_all(b",")?;
+            }
+            write_json_escaped(w, key)?;
+            w.write_all(b":")?;
+            write_json_value(w, value)?;
+            count += 1;
+        }
+    }
+    let mut visitor = FieldsVisitor {
+        out: w,
+        count,
+        err: Ok(()),
+    };
+    record.key_values().visit(&mut visitor).ok();
+    std::mem::replace(&mut visitor.err, Ok(()))?;
+    write!(w, "}}")
+}
+
+/// Renders `record` as a single-line JSON object:
+/// `{"timestamp":"…","level":"INFO","target":"my::mod","module":"my::mod","file":"…","line":42,"fields":{"a":17,"b":"foo"},"message":"…"}`.
+///
+/// `fields` holds the static pairs installed via [`Logger::add_kv`](crate::Logger::add_kv),
+/// followed by whatever the record's own [`log::kv`] source carries, e.g. from
+/// `info!(a = 17, b = "foo"; "message")`; it's `{}` when neither is present.
+/// `timestamp` is rendered from `now`; [`Logger::use_utc`](crate::Logger::use_utc), which
+/// switches only the file writer's rotation and file-naming timestamps, does not change it.
+///
+/// Register it with [`Logger::format`](crate::Logger::format) (or any of the `format_for_*`
+/// builder methods) to make `log_to_file` output directly ingestible by log shippers and
+/// ELK-style pipelines, without a post-processing step.
+pub fn json_format(
+    w: &mut dyn Write,
+    now: &mut DeferredNow,
+    record: &Record,
+) -> std::io::Result<()> {
+    write!(w, "{{\"timestamp\":")?;
+    write_json_escaped(w, &now.now().to_rfc3339_opts(chrono::SecondsFormat::Micros, false))?;
+    write!(w, ",\"level\":\"{}\",\"target\":", record.level())?;
+    write_json_escaped(w, record.target())?;
+    write!(w, ",\"module\":")?;
+    write_json_escaped(w, record.module_path().unwrap_or("<unnamed>"))?;
+    write!(w, ",\"file\":")?;
+    write_json_escaped(w, record.file().unwrap_or("<unknown>"))?;
+    write!(w, ",\"line\":")?;
+    match record.line() {
+        Some(line) => write!(w, "{}", line)?,
+        None => write!(w, "null")?,
+    }
+    write!(w, ",\"fields\":")?;
+    write_fields(w, record)?;
+    write!(w, ",\"message\":")?;
+    write_json_escaped(w, &record.args().to_string())?;
+    write!(w, "}}")
+}
+
+/// Like [`json_format`], but drops `module`, `file`, and `line` to shrink each line, for setups
+/// that ingest at high volume and don't need call-site location on every record (it's still
+/// available via [`default_format`]/[`detailed_format`] or `json_format` where wanted):
+/// `{"timestamp":"…","level":"INFO","target":"my::mod","fields":{"a":17,"b":"foo"},"message":"…"}`.
+///
+/// Register it the same way as [`json_format`], via [`Logger::format`](crate::Logger::format)
+/// or any of the `format_for_*` builder methods.
+pub fn json_compact_format(
+    w: &mut dyn Write,
+    now: &mut DeferredNow,
+    record: &Record,
+) -> std::io::Result<()> {
+    write!(w, "{{\"timestamp\":")?;
+    write_json_escaped(w, &now.now().to_rfc3339_opts(chrono::SecondsFormat::Micros, false))?;
+    write!(w, ",\"level\":\"{}\",\"target\":", record.level())?;
+    write_json_escaped(w, record.target())?;
+    write!(w, ",\"fields\":")?;
+    write_fields(w, record)?;
+    write!(w, ",\"message\":")?;
+    write_json_escaped(w, &record.args().to_string())?;
+    write!(w, "}}")
+}
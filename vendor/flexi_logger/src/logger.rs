@@ -4,11 +4,17 @@ use crate::formats::default_format;
 #[cfg(feature = "atty")]
 use crate::formats::AdaptiveFormat;
 use crate::primary_writer::PrimaryWriter;
-use crate::writers::{FileLogWriter, FileLogWriterBuilder, FlWriteMode, LogWriter};
+#[cfg(feature = "journal")]
+use crate::writers::JournalLogWriter;
+use crate::writers::{
+    FileLogWriter, FileLogWriterBuilder, FlWriteMode, LogWriter, OverflowPolicy, SyslogWriterBuilder,
+};
 use crate::{
-    Cleanup, Criterion, FileSpec, FlexiLoggerError, FormatFunction, LogSpecification, LoggerHandle,
-    Naming, DEFAULT_BUFFER_CAPACITY, DEFAULT_FLUSH_INTERVAL,
+    Age, Cleanup, Criterion, FileSpec, FlexiLoggerError, FormatFunction, LogSpecification,
+    LoggerHandle, Naming, DEFAULT_BUFFER_CAPACITY, DEFAULT_FLUSH_INTERVAL,
 };
+#[cfg(feature = "specfile_without_notification")]
+use crate::logger_handle::LogSpecSubscriber;
 #[cfg(feature = "async")]
 use crate::{DEFAULT_MESSAGE_CAPA, DEFAULT_POOL_CAPA};
 #[cfg(feature = "specfile")]
@@ -62,15 +68,17 @@ pub struct Logger {
     #[cfg(feature = "colors")]
     o_palette: Option<String>,
     o_flush_wait: Option<std::time::Duration>,
+    o_use_print: bool,
     flwb: FileLogWriterBuilder,
     other_writers: HashMap<String, Box<dyn LogWriter>>,
     filter: Option<Box<dyn LogLineFilter + Send + Sync>>,
+    kv_fields: Vec<(String, String)>,
 }
 
 enum LogTarget {
     StdErr,
     StdOut,
-    Multi(bool, Option<Box<dyn LogWriter>>),
+    Multi(bool, Vec<Box<dyn LogWriter>>),
 }
 
 /// Create a Logger instance and define how to access the (initial)
@@ -153,9 +161,11 @@ impl Logger {
             #[cfg(feature = "colors")]
             o_palette: None,
             o_flush_wait: None,
+            o_use_print: false,
             flwb: FileLogWriter::builder(FileSpec::default()),
             other_writers: HashMap::<String, Box<dyn LogWriter>>::new(),
             filter: None,
+            kv_fields: Vec::new(),
         }
     }
 }
@@ -185,7 +195,7 @@ impl Logger {
     /// You can duplicate to stdout and stderr, and you can add additional writers.
     #[must_use]
     pub fn log_to_file(mut self, file_spec: FileSpec) -> Self {
-        self.log_target = LogTarget::Multi(true, None);
+        self.log_target = LogTarget::Multi(true, Vec::new());
         self.flwb = self.flwb.file_spec(file_spec);
         self
     }
@@ -195,21 +205,123 @@ impl Logger {
     /// You can duplicate to stdout and stderr, and you can add additional writers.
     #[must_use]
     pub fn log_to_writer(mut self, w: Box<dyn LogWriter>) -> Self {
-        self.log_target = LogTarget::Multi(false, Some(w));
+        self.log_target = LogTarget::Multi(false, vec![w]);
         self
     }
 
+    /// Log is sent to a syslog daemon, as configured by the given [`SyslogWriterBuilder`].
+    ///
+    /// Unlike the other `log_to_*` methods, this one connects to the syslog destination
+    /// immediately (rather than deferring it to [`Logger::build`]/[`Logger::start`]), so it
+    /// can fail right here if the destination isn't reachable -- except for
+    /// [`SyslogTransport::Tcp`]/`TcpTls`, whose connection (and any later reconnection) happens
+    /// in a background thread, so a down collector at startup doesn't fail this call or block
+    /// logging calls later on.
+    ///
+    /// You can duplicate to stdout and stderr, and you can add additional writers.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::OutputIo`] if connecting to the configured syslog destination fails.
+    pub fn log_to_syslog(
+        mut self,
+        builder: SyslogWriterBuilder,
+    ) -> Result<Self, FlexiLoggerError> {
+        self.log_target = LogTarget::Multi(false, vec![Box::new(builder.try_build()?)]);
+        Ok(self)
+    }
+
+    /// Log is sent as native, structured datagrams to the local `systemd-journald` socket.
+    ///
+    /// Like [`Logger::log_to_syslog`], this connects to the destination immediately, so it
+    /// can fail right here if the journal socket isn't reachable.
+    ///
+    /// You can duplicate to stdout and stderr, and you can add additional writers.
+    ///
+    /// Only available with feature `journal`, and only on Linux.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::JournalSocket`] if connecting to the journal socket fails.
+    #[cfg(feature = "journal")]
+    pub fn log_to_journal(mut self) -> Result<Self, FlexiLoggerError> {
+        let max_level = self.spec.max_level();
+        self.log_target = LogTarget::Multi(false, vec![Box::new(JournalLogWriter::try_new(max_level)?)]);
+        Ok(self)
+    }
+
     /// Log is written to a file, as with [`Logger::log_to_file`], _and_ to an alternative
     /// [`LogWriter`] implementation.
     ///
     /// And you can duplicate to stdout and stderr, and you can add additional writers.
     #[must_use]
     pub fn log_to_file_and_writer(mut self, file_spec: FileSpec, w: Box<dyn LogWriter>) -> Self {
-        self.log_target = LogTarget::Multi(true, Some(w));
+        self.log_target = LogTarget::Multi(true, vec![w]);
         self.flwb = self.flwb.file_spec(file_spec);
         self
     }
 
+    /// Adds another [`LogWriter`] that every record is written to, on top of a file or writer
+    /// `log_target` that is already configured.
+    ///
+    /// Can be called more than once, to fan out to several independent writers at once --
+    /// e.g. a file (via [`Logger::log_to_file`]), a syslog writer, a custom network writer,
+    /// an in-memory ring buffer for tests. A write failure on one of them is reported back
+    /// (the first error encountered), but doesn't prevent the remaining writers from
+    /// receiving the record.
+    ///
+    /// If the `log_target` is stdout or stderr (stderr being the default), this has the same
+    /// effect as [`Logger::log_to_writer`]: the writer replaces that target, and, like with
+    /// that method, you can duplicate to stdout and stderr,
+    /// and it's independent from [`Logger::add_writer`], which registers a writer that's
+    /// addressed by name rather than receiving every record.
+    #[must_use]
+    pub fn add_log_writer(mut self, w: Box<dyn LogWriter>) -> Self {
+        self.log_target = match self.log_target {
+            LogTarget::Multi(use_file, mut writers) => {
+                writers.push(w);
+                LogTarget::Multi(use_file, writers)
+            }
+            LogTarget::StdOut | LogTarget::StdErr => LogTarget::Multi(false, vec![w]),
+        };
+        self
+    }
+
+    /// Shorthand for the common "log to a daily-rotated file, keep a week of history, and
+    /// tell the user where to find it" setup, spelled out longhand in the
+    /// [crate-level docs](crate::code_examples) as `log_to_file` + `rotate` +
+    /// `print_message`:
+    ///
+    /// ```rust
+    /// # use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Logger, Naming};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// Logger::try_with_str("info")?
+    ///     .log_to_file(FileSpec::default())
+    ///     .rotate(
+    ///         Criterion::Age(Age::Day),
+    ///         Naming::Timestamps,
+    ///         Cleanup::KeepLogFiles(7),
+    ///     )
+    ///     .print_message()
+    ///     .start()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Use [`Logger::log_to_file`]/[`Logger::rotate`] directly if you need a different
+    /// rotation criterion, naming scheme, or retention count.
+    #[must_use]
+    pub fn file_default() -> Self {
+        Self::with(LogSpecification::parse("info").unwrap(/* "info" is always valid */))
+            .log_to_file(FileSpec::default())
+            .rotate(
+                Criterion::Age(Age::Day),
+                Naming::Timestamps,
+                Cleanup::KeepLogFiles(7),
+            )
+            .print_message()
+    }
+
     /// Log is processed, including duplication, but not written to any destination.
     ///
     /// This can be useful e.g. for running application tests with all log-levels active and still
@@ -223,7 +335,7 @@ impl Logger {
     /// [`Logger::duplicate_to_stdout`] and [`Logger::duplicate_to_stderr`].
     #[must_use]
     pub fn do_not_log(mut self) -> Self {
-        self.log_target = LogTarget::Multi(false, None);
+        self.log_target = LogTarget::Multi(false, Vec::new());
         self
     }
 
@@ -238,6 +350,9 @@ impl Logger {
     /// Makes the logger write messages with the specified minimum severity additionally to stderr.
     ///
     /// Does not work with [`Logger::log_to_stdout`] or [`Logger::log_to_stderr`].
+    ///
+    /// This is only the level used at startup; once the logger is running, it can be raised or
+    /// lowered at any time with [`LoggerHandle::adapt_duplication_to_stderr`](crate::LoggerHandle::adapt_duplication_to_stderr).
     #[must_use]
     pub fn duplicate_to_stderr(mut self, dup: Duplicate) -> Self {
         self.duplicate_err = dup;
@@ -247,6 +362,9 @@ impl Logger {
     /// Makes the logger write messages with the specified minimum severity additionally to stdout.
     ///
     /// Does not work with [`Logger::log_to_stdout`] or [`Logger::log_to_stderr`].
+    ///
+    /// This is only the level used at startup; once the logger is running, it can be raised or
+    /// lowered at any time with [`LoggerHandle::adapt_duplication_to_stdout`](crate::LoggerHandle::adapt_duplication_to_stdout).
     #[must_use]
     pub fn duplicate_to_stdout(mut self, dup: Duplicate) -> Self {
         self.duplicate_out = dup;
@@ -449,6 +567,25 @@ impl Logger {
         self
     }
 
+    /// Shorthand for `.filter(Box::new(dedup))`: collapses consecutive, identical log messages
+    /// into the first occurrence plus a `"... last message repeated N times ..."` summary,
+    /// instead of writing every repetition. See [`crate::Dedup`].
+    #[must_use]
+    pub fn dedup(self, dedup: crate::filter::Dedup) -> Self {
+        self.filter(Box::new(dedup))
+    }
+
+    /// Adds a static key-value pair that gets merged into the `fields` object of every
+    /// [`json_format`](crate::json_format)/[`json_compact_format`](crate::json_compact_format)
+    /// record, e.g. a `service` or `version` tag you want on every line without repeating it at
+    /// each call site. Can be called repeatedly to add several pairs. Has no effect with
+    /// non-JSON format functions.
+    #[must_use]
+    pub fn add_kv<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.kv_fields.push((key.into(), value.into()));
+        self
+    }
+
     /// Makes the logger append to the specified output file, if it exists already;
     /// by default, the file would be truncated.
     ///
@@ -482,6 +619,18 @@ impl Logger {
         self
     }
 
+    /// Watches the directory of the active log file and reopens it if an external tool
+    /// (e.g. `logrotate`) removes or renames it out from under us, rather than leaving us
+    /// writing into an unlinked inode.
+    ///
+    /// Only available with feature `external_rotation_watch`.
+    #[cfg(feature = "external_rotation_watch")]
+    #[must_use]
+    pub fn watch_external_rotation(mut self) -> Self {
+        self.flwb = self.flwb.watch_external_rotation();
+        self
+    }
+
     /// Registers a [`LogWriter`] implementation under the given target name.
     ///
     /// The target name must not start with an underscore.
@@ -502,6 +651,7 @@ impl Logger {
     pub fn write_mode(mut self, write_mode: WriteMode) -> Self {
         self.flwb = self.flwb.write_mode(write_mode.get_fl_write_mode());
         self.o_flush_wait = write_mode.get_duration();
+        self.o_use_print = matches!(write_mode, WriteMode::SupportCapture);
         self
     }
 
@@ -511,6 +661,18 @@ impl Logger {
         self.flwb = self.flwb.use_windows_line_ending();
         self
     }
+
+    /// Renders the rotation and file-naming timestamps of the [`FileLogWriter`] in UTC rather
+    /// than in local time.
+    ///
+    /// Local time is ambiguous across DST transitions and hard to correlate when aggregating
+    /// logs from machines in different time zones. This setting does not change the
+    /// timestamps that the format functions render into the log lines from `DeferredNow`.
+    #[must_use]
+    pub fn use_utc(mut self) -> Self {
+        self.flwb = self.flwb.use_utc();
+        self
+    }
 }
 
 /// Alternative set of methods to control the behavior of the Logger.
@@ -618,54 +780,85 @@ impl Logger {
     pub fn build(self) -> Result<(Box<dyn log::Log>, LoggerHandle), FlexiLoggerError> {
         #[cfg(feature = "colors")]
         crate::formats::set_palette(&self.o_palette)?;
+        crate::formats::set_static_kv(self.kv_fields.clone());
 
         let a_primary_writer = Arc::new(match self.log_target {
-            LogTarget::StdOut => {
-                PrimaryWriter::stdout(self.format_for_stdout, &self.flwb.buffersize())
-            }
-            LogTarget::StdErr => {
-                PrimaryWriter::stderr(self.format_for_stderr, &self.flwb.buffersize())
-            }
-            LogTarget::Multi(use_file, mut o_writer) => PrimaryWriter::multi(
-                self.duplicate_err,
-                self.duplicate_out,
-                self.format_for_stderr,
+            LogTarget::StdOut => PrimaryWriter::stdout(
                 self.format_for_stdout,
-                if use_file {
-                    Some(Box::new(
-                        self.flwb.format(self.format_for_file).try_build()?,
-                    ))
-                } else {
-                    None
-                },
-                {
-                    if let Some(ref mut writer) = o_writer {
-                        writer.format(self.format_for_writer);
-                    }
-                    o_writer
-                },
+                &self.flwb.buffersize(),
+                self.o_use_print,
+                #[cfg(feature = "async")]
+                self.flwb.async_buffersize(),
+            ),
+            LogTarget::StdErr => PrimaryWriter::stderr(
+                self.format_for_stderr,
+                &self.flwb.buffersize(),
+                self.o_use_print,
+                #[cfg(feature = "async")]
+                self.flwb.async_buffersize(),
             ),
+            LogTarget::Multi(use_file, mut o_writers) => {
+                for writer in &mut o_writers {
+                    writer.format(self.format_for_writer);
+                }
+                PrimaryWriter::multi(
+                    self.duplicate_err,
+                    self.duplicate_out,
+                    self.format_for_stderr,
+                    self.format_for_stdout,
+                    self.o_use_print,
+                    if use_file {
+                        Some(Box::new(
+                            self.flwb.format(self.format_for_file).try_build()?,
+                        ))
+                    } else {
+                        None
+                    },
+                    o_writers,
+                    #[cfg(feature = "async")]
+                    self.flwb.async_buffersize(),
+                )
+            }
         });
 
         let a_other_writers = Arc::new(self.other_writers);
+        let a_filter: Option<Arc<dyn LogLineFilter + Send + Sync>> = self.filter.map(Arc::from);
 
-        if let Some(wait_time) = self.o_flush_wait {
+        let o_flusher = if let Some(wait_time) = self.o_flush_wait {
             let pw = Arc::clone(&a_primary_writer);
             let ows = Arc::clone(&a_other_writers);
-            std::thread::Builder::new()
+            let filter = a_filter.clone();
+            let (sender, receiver): (Sender<()>, Receiver<()>) = channel();
+            let join_handle = std::thread::Builder::new()
                 .name("flexi_logger-flusher".to_string())
                 .stack_size(128)
-                .spawn(move || {
-                    let (_sender, receiver): (Sender<()>, Receiver<()>) = channel();
-                    loop {
-                        receiver.recv_timeout(wait_time).ok();
-                        pw.flush().ok();
-                        for w in ows.values() {
-                            w.flush().ok();
+                .spawn(move || loop {
+                    match receiver.recv_timeout(wait_time) {
+                        Ok(()) | Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                            pw.flush().ok();
+                            for w in ows.values() {
+                                w.flush().ok();
+                            }
+                            // Gives an installed filter (e.g. `Dedup`) the same periodic
+                            // chance to emit whatever it's still holding back, so a
+                            // suppression stuck at some count surfaces within this flush
+                            // interval even without new, different log traffic.
+                            if let Some(ref filter) = filter {
+                                filter
+                                    .flush_pending(&mut crate::deferred_now::DeferredNow::new(), &*pw)
+                                    .ok();
+                            }
                         }
+                        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
                     }
                 })?;
-        }
+            // Keeping `sender` alive (rather than letting the spawned closure's own sender-less
+            // channel end dangle) is what lets `LoggerHandle::shutdown` stop this thread: dropping
+            // it later disconnects `receiver`, which the loop above treats as its exit signal.
+            Some((sender, join_handle))
+        } else {
+            None
+        };
 
         let max_level = self.spec.max_level();
         let a_l_spec = Arc::new(RwLock::new(self.spec));
@@ -674,10 +867,13 @@ impl Logger {
             Arc::clone(&a_l_spec),
             Arc::clone(&a_primary_writer),
             Arc::clone(&a_other_writers),
-            self.filter,
+            a_filter.clone(),
         );
 
-        let handle = LoggerHandle::new(a_l_spec, a_primary_writer, a_other_writers);
+        let handle = LoggerHandle::new(a_l_spec, a_primary_writer, a_other_writers, a_filter);
+        if let Some((sender, join_handle)) = o_flusher {
+            handle.register_flusher(sender, join_handle);
+        }
         handle.reconfigure(max_level);
         Ok((Box::new(flexi_logger), handle))
     }
@@ -733,7 +929,9 @@ impl Logger {
     /// You can subsequently edit and modify the file according to your needs,
     /// while the program is running, and it will immediately take your changes into account.
     ///
-    /// Currently only toml-files are supported, the file suffix thus must be `.toml`.
+    /// The file format is derived from the file suffix: `.toml` (the default), or, with the
+    /// respective additive feature enabled, `.json` (feature `specfile_json`) or `.yaml`/`.yml`
+    /// (feature `specfile_yaml`).
     ///
     /// The initial spec remains valid if the file cannot be read.
     ///
@@ -776,6 +974,116 @@ impl Logger {
         setup_specfile(specfile, handle.clone())?;
         Ok((boxed_log, handle))
     }
+
+    /// Builds a [`FlexiTracingLayer`](crate::tracing_bridge::FlexiTracingLayer) and a
+    /// [`LoggerHandle`] for it, for use with `tracing_subscriber::Registry`, instead of
+    /// initializing the global `log` logger.
+    ///
+    /// The returned layer reuses this builder's writers (and their rotation, cleanup and
+    /// duplication) for `tracing` events, gated by the maximum level of the handle's own,
+    /// live-reloadable [`LogSpecification`] (its per-module directives are not applied); see
+    /// [`Logger::start_with_specfile`] to also keep that spec in sync with a spec file.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of [`FlexiLoggerError`] can occur.
+    #[cfg(feature = "trc")]
+    pub fn build_with_tracing(
+        self,
+    ) -> Result<(crate::tracing_bridge::FlexiTracingLayer, LoggerHandle), FlexiLoggerError> {
+        let (_boxed_log, handle) = self.build()?;
+        let layer = crate::tracing_bridge::tracing_layer(handle.clone());
+        Ok((layer, handle))
+    }
+}
+
+// The format a specfile is parsed as / serialized to, derived from its file suffix.
+#[cfg(feature = "specfile_without_notification")]
+#[derive(Clone, Copy)]
+enum SpecfileFormat {
+    Toml,
+    #[cfg(feature = "specfile_json")]
+    Json,
+    #[cfg(feature = "specfile_yaml")]
+    Yaml,
+}
+
+#[cfg(feature = "specfile_without_notification")]
+fn specfile_format(specfile: &Path) -> Result<SpecfileFormat, FlexiLoggerError> {
+    match specfile
+        .extension()
+        .unwrap_or_else(|| std::ffi::OsStr::new(""))
+        .to_str()
+        .unwrap_or("")
+    {
+        "toml" => Ok(SpecfileFormat::Toml),
+        #[cfg(feature = "specfile_json")]
+        "json" => Ok(SpecfileFormat::Json),
+        #[cfg(feature = "specfile_yaml")]
+        "yaml" | "yml" => Ok(SpecfileFormat::Yaml),
+        _ => Err(FlexiLoggerError::SpecfileExtension(
+            "only spec files with extension toml, json, or yaml/yml are supported",
+        )),
+    }
+}
+
+#[cfg(feature = "specfile_without_notification")]
+fn parse_specfile(format: SpecfileFormat, s: &str) -> Result<LogSpecification, FlexiLoggerError> {
+    match format {
+        SpecfileFormat::Toml => LogSpecification::from_toml(s),
+        #[cfg(feature = "specfile_json")]
+        SpecfileFormat::Json => LogSpecification::from_json(s),
+        #[cfg(feature = "specfile_yaml")]
+        SpecfileFormat::Yaml => LogSpecification::from_yaml(s),
+    }
+}
+
+// Writes a short, commented explanation of the file's syntax ahead of the actual spec, so a
+// freshly created specfile is self-documenting for whoever edits it next. TOML and YAML both
+// use `#` for comments; JSON doesn't support comments at all, so nothing is written for it.
+#[cfg(feature = "specfile_without_notification")]
+fn write_specfile_template(
+    format: SpecfileFormat,
+    file: &mut std::fs::File,
+) -> Result<(), FlexiLoggerError> {
+    use std::io::Write;
+    let template = match format {
+        SpecfileFormat::Toml => Some(
+            "# This file is watched by flexi_logger and re-read on every change.\n\
+             # Edit the `global_level` below (off, error, warn, info, debug, trace), or add\n\
+             # per-module overrides under `[modules]`, and the running process picks it up\n\
+             # within about a second -- no restart needed.\n",
+        ),
+        #[cfg(feature = "specfile_yaml")]
+        SpecfileFormat::Yaml => Some(
+            "# This file is watched by flexi_logger and re-read on every change.\n\
+             # Edit `global_level` below (off, error, warn, info, debug, trace), or add\n\
+             # per-module overrides under `modules`, and the running process picks it up\n\
+             # within about a second -- no restart needed.\n",
+        ),
+        #[cfg(feature = "specfile_json")]
+        SpecfileFormat::Json => None,
+    };
+    if let Some(template) = template {
+        file.write_all(template.as_bytes())
+            .map_err(FlexiLoggerError::SpecfileIo)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "specfile_without_notification")]
+fn write_specfile(
+    format: SpecfileFormat,
+    spec: &LogSpecification,
+    file: &mut std::fs::File,
+) -> Result<(), FlexiLoggerError> {
+    match format {
+        SpecfileFormat::Toml => spec.to_toml(file),
+        #[cfg(feature = "specfile_json")]
+        SpecfileFormat::Json => spec.to_json(file),
+        #[cfg(feature = "specfile_yaml")]
+        SpecfileFormat::Yaml => spec.to_yaml(file),
+    }
 }
 
 #[cfg(feature = "specfile_without_notification")]
@@ -788,6 +1096,8 @@ fn setup_specfile<P: AsRef<Path>>(
 
     #[cfg(feature = "specfile")]
     {
+        let format = specfile_format(&specfile)?;
+
         // Now that the file exists, we can canonicalize the path
         let specfile = specfile
             .canonicalize()
@@ -799,22 +1109,31 @@ fn setup_specfile<P: AsRef<Path>>(
         let mut watcher = watcher(tx, debouncing_delay)?;
         watcher.watch(&specfile.parent().unwrap(), RecursiveMode::NonRecursive)?;
 
+        // Polled at the top of every loop iteration below, so `LoggerHandle::shutdown` has a
+        // way to stop this thread even though the notify channel itself has no shutdown signal.
+        let stop_watching = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stop_watching_for_thread = Arc::clone(&stop_watching);
+        let handle_for_registration = handle.clone();
+
         // in a separate thread, reread the specfile when it was updated
-        std::thread::Builder::new()
+        let join_handle = std::thread::Builder::new()
             .name("flexi_logger-specfile-watcher".to_string())
             .stack_size(128 * 1024)
             .spawn(move || {
                 let _anchor_for_watcher = watcher; // keep it alive!
                 loop {
-                    match rx.recv() {
+                    if stop_watching_for_thread.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+                    match rx.recv_timeout(std::time::Duration::from_millis(500)) {
                         Ok(debounced_event) => match debounced_event {
                             DebouncedEvent::Create(ref path) | DebouncedEvent::Write(ref path) => {
                                 if path.canonicalize().map(|x| x == specfile).unwrap_or(false) {
                                     match log_spec_string_from_file(&specfile)
                                         .map_err(FlexiLoggerError::SpecfileIo)
-                                        .and_then(|s| LogSpecification::from_toml(&s))
+                                        .and_then(|s| parse_specfile(format, &s))
                                     {
-                                        Ok(spec) => handle.set_new_spec(spec),
+                                        Ok(spec) => handle.update(spec),
                                         Err(e) => eprintln!(
                                             "[flexi_logger] rereading the log specification file \
                                              failed with {:?}, \
@@ -826,12 +1145,12 @@ fn setup_specfile<P: AsRef<Path>>(
                             }
                             _event => {}
                         },
-                        Err(e) => {
-                            eprintln!("[flexi_logger] error while watching the specfile: {:?}", e)
-                        }
+                        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
                     }
                 }
             })?;
+        handle_for_registration.register_specfile_watcher(stop_watching, join_handle);
     }
     Ok(())
 }
@@ -843,21 +1162,11 @@ pub(crate) fn synchronize_handle_with_specfile(
     handle: &mut LoggerHandle,
     specfile: &Path,
 ) -> Result<(), FlexiLoggerError> {
-    if specfile
-        .extension()
-        .unwrap_or_else(|| std::ffi::OsStr::new(""))
-        .to_str()
-        .unwrap_or("")
-        != "toml"
-    {
-        return Err(FlexiLoggerError::SpecfileExtension(
-            "only spec files with extension toml are supported",
-        ));
-    }
+    let format = specfile_format(specfile)?;
 
     if Path::is_file(specfile) {
         let s = log_spec_string_from_file(specfile).map_err(FlexiLoggerError::SpecfileIo)?;
-        handle.set_new_spec(LogSpecification::from_toml(&s)?);
+        handle.set_new_spec(parse_specfile(format, &s)?);
     } else {
         if let Some(specfolder) = specfile.parent() {
             std::fs::DirBuilder::new()
@@ -871,11 +1180,15 @@ pub(crate) fn synchronize_handle_with_specfile(
             .open(specfile)
             .map_err(FlexiLoggerError::SpecfileIo)?;
 
-        handle
-            .current_spec()
-            .read()
-            .map_err(|_e| FlexiLoggerError::Poison)?
-            .to_toml(&mut file)?;
+        write_specfile_template(format, &mut file)?;
+        write_specfile(
+            format,
+            &handle
+                .current_spec()
+                .read()
+                .map_err(|_e| FlexiLoggerError::Poison)?,
+            &mut file,
+        )?;
     }
     Ok(())
 }
@@ -909,6 +1222,21 @@ pub enum Duplicate {
     All,
 }
 
+impl Duplicate {
+    // The level up to which this setting lets messages through, for folding a duplication
+    // setting into the effective `log::set_max_level`.
+    pub(crate) fn to_level_filter(self) -> log::LevelFilter {
+        match self {
+            Self::None => log::LevelFilter::Off,
+            Self::Error => log::LevelFilter::Error,
+            Self::Warn => log::LevelFilter::Warn,
+            Self::Info => log::LevelFilter::Info,
+            Self::Debug => log::LevelFilter::Debug,
+            Self::Trace | Self::All => log::LevelFilter::Trace,
+        }
+    }
+}
+
 /// Describes if the log output should be written synchronously or asynchronously,
 /// and if and how file I/O should be buffered and flushed.
 ///
@@ -974,11 +1302,33 @@ pub enum WriteMode {
     /// This might be handy if you want to minimize I/O but don't want to create
     /// the extra thread for flushing and don't care if log lines appear with delay.
     BufferDontFlush,
+
+    /// Sends log messages through a bounded queue to a dedicated writer thread, which owns
+    /// the file and performs the write, the rotation, and the cleanup, so that logging calls
+    /// never block on disk I/O.
+    ///
+    /// `queue_size` bounds how many not-yet-written messages can pile up; `on_full` decides
+    /// what happens to a new message when that bound is reached.
+    BackgroundThread {
+        /// Capacity of the bounded queue feeding the writer thread.
+        queue_size: usize,
+        /// What to do when the queue is full.
+        on_full: OverflowPolicy,
+    },
+
+    /// Like `Direct` (unbuffered, synchronous), but routes stdout/stderr output through the
+    /// `print!`/`eprintln!` macros instead of writing to `io::stdout()`/`io::stderr()` directly.
+    ///
+    /// `cargo test` only intercepts the `print!`/`eprint!` family on the test thread, so with
+    /// any other write mode, log output produced during a `#[test]` is silently dropped instead
+    /// of being captured and shown for failing tests. Use this mode to make logging useful
+    /// under the test harness; it is slower than `Direct` and is not meant for production use.
+    SupportCapture,
 }
 impl WriteMode {
     fn get_fl_write_mode(&self) -> FlWriteMode {
         match self {
-            Self::Direct => FlWriteMode::DontBuffer,
+            Self::Direct | Self::SupportCapture => FlWriteMode::DontBuffer,
             Self::BufferDontFlush | Self::BufferAndFlush => {
                 FlWriteMode::Buffer(DEFAULT_BUFFER_CAPACITY)
             }
@@ -996,12 +1346,16 @@ impl WriteMode {
                 message_capa,
                 flush_interval: _,
             } => FlWriteMode::BufferAsync(*bufsize, *pool_capa, *message_capa),
+            Self::BackgroundThread {
+                queue_size,
+                on_full,
+            } => FlWriteMode::BackgroundThread(*queue_size, *on_full),
         }
     }
     fn get_duration(&self) -> Option<Duration> {
         #[allow(clippy::match_same_arms)]
         match self {
-            Self::Direct | Self::BufferDontFlush => None,
+            Self::Direct | Self::SupportCapture | Self::BufferDontFlush => None,
             Self::BufferAndFlush => Some(DEFAULT_FLUSH_INTERVAL),
             Self::BufferAndFlushWith(_, flush_interval) => Some(*flush_interval),
             #[cfg(feature = "async")]
@@ -1013,6 +1367,23 @@ impl WriteMode {
                 message_capa: _,
                 flush_interval,
             } => Some(*flush_interval),
+            // the writer thread does its own unbuffered writes; no periodic flusher needed.
+            Self::BackgroundThread { .. } => None,
         }
     }
 }
+
+/// Gets you logging to stderr, with the default adaptive format, in one call: reads the log
+/// specification from `RUST_LOG`, falling back to `info` if it's unset or fails to parse.
+///
+/// This is the `try_with_env_or_str("info")?.start()?` recipe for scripts and examples that
+/// don't want to handle a [`FlexiLoggerError`] or keep the [`LoggerHandle`] around themselves;
+/// the handle is leaked so buffered [`WriteMode`]s stay valid for the rest of the program.
+/// Reach for [`Logger::try_with_env_or_str`] directly if you need the handle (e.g. to
+/// [`flush`](LoggerHandle::flush) or [`reconfigure`](LoggerHandle::set_new_spec) later) or want
+/// to handle a misconfigured `RUST_LOG` yourself instead of silently falling back.
+pub fn init() {
+    if let Ok(handle) = Logger::try_with_env_or_str("info").and_then(Logger::start) {
+        std::mem::forget(handle);
+    }
+}
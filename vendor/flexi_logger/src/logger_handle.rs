@@ -1,8 +1,23 @@
+use crate::filter::LogLineFilter;
+use crate::logger::Duplicate;
 use crate::primary_writer::PrimaryWriter;
 use crate::writers::{FileLogWriterBuilder, LogWriter};
 use crate::{FlexiLoggerError, LogSpecification};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+
+// Lets `LoggerHandle::shutdown` signal and join the flusher and specfile-watcher threads
+// that `Logger::build` and `setup_specfile` spawn. Shared (via the `Arc<Mutex<_>>` on
+// `LoggerHandle`) so that a handle clone passed into `setup_specfile` can register the
+// watcher thread it spawns on behalf of the handle it was cloned from.
+#[derive(Default)]
+struct ThreadShutdown {
+    flusher: Option<(Sender<()>, JoinHandle<()>)>,
+    specfile_watcher: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
 
 /// Shuts down the logger when dropped, and allows reconfiguring the logger programmatically.
 ///
@@ -85,6 +100,25 @@ pub struct LoggerHandle {
     spec_stack: Vec<LogSpecification>,
     primary_writer: Arc<PrimaryWriter>,
     other_writers: Arc<HashMap<String, Box<dyn LogWriter>>>,
+    filter: Option<Arc<dyn LogLineFilter + Send + Sync>>,
+    thread_shutdown: Arc<Mutex<ThreadShutdown>>,
+}
+
+/// Abstracts over "something that a freshly parsed [`LogSpecification`] can be pushed to".
+///
+/// [`LoggerHandle`] is the only implementor today (its `update` just forwards to
+/// [`LoggerHandle::set_new_spec`]), but giving the specfile watcher a trait to push updates
+/// through, rather than a concrete `LoggerHandle`, keeps the door open for e.g. a subscriber
+/// that also logs each reload, or forwards the update to more than one handle.
+pub trait LogSpecSubscriber {
+    /// Applies a freshly parsed `LogSpecification`.
+    fn update(&mut self, spec: LogSpecification);
+}
+
+impl LogSpecSubscriber for LoggerHandle {
+    fn update(&mut self, spec: LogSpecification) {
+        self.set_new_spec(spec);
+    }
 }
 
 impl LoggerHandle {
@@ -92,12 +126,35 @@ impl LoggerHandle {
         spec: Arc<RwLock<LogSpecification>>,
         primary_writer: Arc<PrimaryWriter>,
         other_writers: Arc<HashMap<String, Box<dyn LogWriter>>>,
+        filter: Option<Arc<dyn LogLineFilter + Send + Sync>>,
     ) -> Self {
         Self {
             spec,
             spec_stack: Vec::default(),
             primary_writer,
             other_writers,
+            filter,
+            thread_shutdown: Arc::new(Mutex::new(ThreadShutdown::default())),
+        }
+    }
+
+    // Called once from `Logger::build`, if a flush thread was spawned, so `shutdown()` can
+    // later signal and join it.
+    pub(crate) fn register_flusher(&self, sender: Sender<()>, join_handle: JoinHandle<()>) {
+        if let Ok(mut guard) = self.thread_shutdown.lock() {
+            guard.flusher = Some((sender, join_handle));
+        }
+    }
+
+    // Called once from `setup_specfile`, on the handle clone it was given, so `shutdown()`
+    // (on any clone, since the registration is shared) can later stop and join the watcher.
+    pub(crate) fn register_specfile_watcher(
+        &self,
+        stop_flag: Arc<AtomicBool>,
+        join_handle: JoinHandle<()>,
+    ) {
+        if let Ok(mut guard) = self.thread_shutdown.lock() {
+            guard.specfile_watcher = Some((stop_flag, join_handle));
         }
     }
 
@@ -106,6 +163,28 @@ impl LoggerHandle {
         Arc::clone(&self.spec)
     }
 
+    // Like `current_spec`, but available independently of the specfile feature set, for
+    // consumers (e.g. the `trc` tracing bridge) that need to read the live spec without
+    // needing specfile support themselves.
+    #[cfg(feature = "trc")]
+    pub(crate) fn spec_arc(&self) -> Arc<RwLock<LogSpecification>> {
+        Arc::clone(&self.spec)
+    }
+
+    // Writes a single record through the primary writer and every writer in `other_writers`,
+    // the same way `FlexiLogger::log` does for the `log` crate -- used by the `trc` tracing
+    // bridge to reuse flexi_logger's writers (rotation, cleanup, duplication, ...) for events
+    // coming from `tracing` instead of `log`.
+    #[cfg(feature = "trc")]
+    pub(crate) fn write_record(&self, now: &mut crate::deferred_now::DeferredNow, record: &log::Record) {
+        self.primary_writer.write(now, record).ok();
+        for writer in self.other_writers.values() {
+            if record.level() <= writer.max_log_level() {
+                writer.write(now, record).ok();
+            }
+        }
+    }
+
     //
     pub(crate) fn reconfigure(&self, mut max_level: log::LevelFilter) {
         for w in self.other_writers.as_ref().values() {
@@ -173,6 +252,24 @@ impl LoggerHandle {
         for writer in self.other_writers.values() {
             writer.flush().ok();
         }
+        self.flush_filter();
+    }
+
+    // Gives the installed `LogLineFilter` (if any) a chance to emit whatever it's still
+    // holding back -- e.g. a `Dedup` filter's pending "repeated N times" summary -- through
+    // the primary writer. Called from `flush()`, from `shutdown()`/`Drop`, and from the
+    // background flusher thread `Logger::build` spawns, so a suppressed-but-stuck-at-some-count
+    // summary still surfaces on the next periodic flush instead of only when different log
+    // traffic arrives.
+    pub(crate) fn flush_filter(&self) {
+        if let Some(filter) = &self.filter {
+            filter
+                .flush_pending(
+                    &mut crate::deferred_now::DeferredNow::new(),
+                    &*self.primary_writer,
+                )
+                .ok();
+        }
     }
 
     /// Replaces parts of the configuration of the file log writer.
@@ -195,6 +292,70 @@ impl LoggerHandle {
         }
     }
 
+    /// Changes, while the program is running, how much is additionally duplicated to stderr,
+    /// for loggers that write to a file, another writer, or both (see
+    /// [`Logger::duplicate_to_stderr`](crate::Logger::duplicate_to_stderr)).
+    ///
+    /// Complements the already-supported runtime log-spec reconfiguration (see
+    /// [`LoggerHandle::set_new_spec`]), letting tools raise or lower console verbosity on
+    /// demand instead of only at startup.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::Reset`] if the logger doesn't write to a file, another writer, or
+    /// both, since then there is nothing to duplicate to stderr in the first place.
+    pub fn adapt_duplication_to_stderr(&self, dup: Duplicate) -> Result<(), FlexiLoggerError> {
+        self.primary_writer.adapt_duplication_to_stderr(dup)?;
+        self.reconfigure_for_duplication(dup);
+        Ok(())
+    }
+
+    /// Changes, while the program is running, how much is additionally duplicated to stdout,
+    /// for loggers that write to a file, another writer, or both (see
+    /// [`Logger::duplicate_to_stdout`](crate::Logger::duplicate_to_stdout)).
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::Reset`] if the logger doesn't write to a file, another writer, or
+    /// both, since then there is nothing to duplicate to stdout in the first place.
+    pub fn adapt_duplication_to_stdout(&self, dup: Duplicate) -> Result<(), FlexiLoggerError> {
+        self.primary_writer.adapt_duplication_to_stdout(dup)?;
+        self.reconfigure_for_duplication(dup);
+        Ok(())
+    }
+
+    // Folds a newly-set duplication level into the effective `log::set_max_level`, alongside
+    // the current log spec and any `other_writers`, so that raising console duplication above
+    // the spec's own level (e.g. to surface warnings during an incident) doesn't get filtered
+    // out by `log`'s fast-path level gate before `reconfigure` even sees the record.
+    fn reconfigure_for_duplication(&self, dup: Duplicate) {
+        let spec_level = self
+            .spec
+            .read()
+            .map(|spec| spec.max_level())
+            .unwrap_or(log::LevelFilter::Trace);
+        self.reconfigure(std::cmp::max(spec_level, dup.to_level_filter()));
+    }
+
+    /// Closes and recreates the current output file of the file-based primary writer, and asks
+    /// every writer in `other_writers` to do the same via [`LogWriter::reopen`].
+    ///
+    /// Tools like `logrotate` rename or remove the active log file out from under a running
+    /// process; without reopening, all further writes go to the now-unlinked inode and are
+    /// never seen again. Call this from a `SIGHUP` handler (or any signal/trigger your
+    /// deployment uses to announce external rotation) to pick the new file back up.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::ReopenIo`] if recreating the output file fails.
+    pub fn reopen(&self) -> Result<(), FlexiLoggerError> {
+        self.primary_writer.reopen()?;
+        for writer in self.other_writers.values() {
+            writer.reopen().map_err(FlexiLoggerError::ReopenIo)?;
+        }
+        Ok(())
+    }
+
     /// Shutdown all participating writers.
     ///
     /// This method is supposed to be called at the very end of your program, if
@@ -206,11 +367,30 @@ impl LoggerHandle {
     /// - you use your own writer(s), and they need to clean up resources
     ///
     /// See also [`writers::LogWriter::shutdown`](crate::writers::LogWriter::shutdown).
+    ///
+    /// This also signals and joins the flusher thread (if [`Logger::write_mode`] uses one) and
+    /// the specfile-watcher thread (if [`Logger::start_with_specfile`] was used), so that no
+    /// `flexi_logger` thread outlives the call -- useful for test binaries that build a logger
+    /// repeatedly.
+    ///
+    /// [`Logger::write_mode`]: crate::Logger::write_mode
+    /// [`Logger::start_with_specfile`]: crate::Logger::start_with_specfile
     pub fn shutdown(&self) {
+        self.flush_filter();
         self.primary_writer.shutdown();
         for writer in self.other_writers.values() {
             writer.shutdown();
         }
+        if let Ok(mut guard) = self.thread_shutdown.lock() {
+            if let Some((sender, join_handle)) = guard.flusher.take() {
+                drop(sender);
+                join_handle.join().ok();
+            }
+            if let Some((stop_flag, join_handle)) = guard.specfile_watcher.take() {
+                stop_flag.store(true, Ordering::SeqCst);
+                join_handle.join().ok();
+            }
+        }
     }
 
     // Allows checking the logs written so far to the writer
@@ -222,6 +402,7 @@ impl LoggerHandle {
 
 impl Drop for LoggerHandle {
     fn drop(&mut self) {
+        self.flush_filter();
         self.primary_writer.shutdown();
         for writer in self.other_writers.values() {
             writer.shutdown();
@@ -1,371 +1,839 @@
-use crate::deferred_now::DeferredNow;
-use crate::filter::LogLineWriter;
-use crate::logger::Duplicate;
-use crate::writers::{FileLogWriter, FileLogWriterBuilder, LogWriter};
-use crate::{FlexiLoggerError, FormatFunction};
-use log::Record;
-use std::cell::RefCell;
-use std::io::{BufWriter, Write};
-use std::sync::Mutex;
-
-// Writes either to stdout, or to stderr,
-// or to a file (with optional duplication to stderr),
-// or to nowhere (with optional "duplication" to stderr).
-#[allow(clippy::large_enum_variant)]
-pub(crate) enum PrimaryWriter {
-    StdOut(StdOutWriter),
-    StdErr(StdErrWriter),
-    Multi(MultiWriter),
-}
-impl PrimaryWriter {
-    pub fn multi(
-        duplicate_stderr: Duplicate,
-        duplicate_stdout: Duplicate,
-        format_for_stderr: FormatFunction,
-        format_for_stdout: FormatFunction,
-        o_file_writer: Option<Box<FileLogWriter>>,
-        o_other_writer: Option<Box<dyn LogWriter>>,
-    ) -> Self {
-        Self::Multi(MultiWriter {
-            duplicate_stderr,
-            duplicate_stdout,
-            format_for_stderr,
-            format_for_stdout,
-            o_file_writer,
-            o_other_writer,
-        })
-    }
-    pub fn stderr(format: FormatFunction, o_buffer_capacity: &Option<usize>) -> Self {
-        Self::StdErr(StdErrWriter::new(format, o_buffer_capacity))
-    }
-
-    pub fn stdout(format: FormatFunction, o_buffer_capacity: &Option<usize>) -> Self {
-        Self::StdOut(StdOutWriter::new(format, o_buffer_capacity))
-    }
-
-    // Write out a log line.
-    pub fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
-        match *self {
-            Self::StdErr(ref w) => w.write(now, record),
-            Self::StdOut(ref w) => w.write(now, record),
-            Self::Multi(ref w) => w.write(now, record),
-        }
-    }
-
-    // Flush any buffered records.
-    pub fn flush(&self) -> std::io::Result<()> {
-        match *self {
-            Self::StdErr(ref w) => w.flush(),
-            Self::StdOut(ref w) => w.flush(),
-            Self::Multi(ref w) => w.flush(),
-        }
-    }
-
-    pub fn validate_logs(&self, expected: &[(&'static str, &'static str, &'static str)]) {
-        if let Self::Multi(ref w) = *self {
-            w.validate_logs(expected);
-        }
-    }
-
-    pub fn shutdown(&self) {
-        self.flush().ok();
-        if let PrimaryWriter::Multi(writer) = self {
-            writer.shutdown();
-        }
-    }
-}
-
-impl LogLineWriter for PrimaryWriter {
-    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
-        self.write(now, record)
-    }
-}
-
-// `StdErrWriter` writes logs to stderr.
-pub(crate) struct StdErrWriter {
-    format: FormatFunction,
-    writer: ErrWriter,
-}
-enum ErrWriter {
-    Unbuffered(std::io::Stderr),
-    Buffered(Mutex<BufWriter<std::io::Stderr>>),
-}
-impl StdErrWriter {
-    fn new(format: FormatFunction, o_buffer_capacity: &Option<usize>) -> Self {
-        match o_buffer_capacity {
-            Some(capacity) => Self {
-                format,
-                writer: ErrWriter::Buffered(Mutex::new(BufWriter::with_capacity(
-                    *capacity,
-                    std::io::stderr(),
-                ))),
-            },
-            None => Self {
-                format,
-                writer: ErrWriter::Unbuffered(std::io::stderr()),
-            },
-        }
-    }
-    #[inline]
-    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
-        match &self.writer {
-            ErrWriter::Unbuffered(stderr) => {
-                let mut w = stderr.lock();
-                write_buffered(self.format, now, record, &mut w)
-            }
-            ErrWriter::Buffered(mbuf_w) => {
-                let mut w = mbuf_w.lock().map_err(|e| poison_err("stderr", &e))?;
-                write_buffered(self.format, now, record, &mut *w)
-            }
-        }
-    }
-
-    #[inline]
-    fn flush(&self) -> std::io::Result<()> {
-        match &self.writer {
-            ErrWriter::Unbuffered(stderr) => {
-                let mut w = stderr.lock();
-                w.flush()
-            }
-            ErrWriter::Buffered(mbuf_w) => {
-                let mut w = mbuf_w.lock().map_err(|e| poison_err("stderr", &e))?;
-                w.flush()
-            }
-        }
-    }
-}
-
-fn poison_err(s: &'static str, _e: &dyn std::error::Error) -> std::io::Error {
-    std::io::Error::new(std::io::ErrorKind::Other, s)
-}
-
-// `StdOutWriter` writes logs to stdout.
-pub(crate) struct StdOutWriter {
-    format: FormatFunction,
-    writer: OutWriter,
-}
-enum OutWriter {
-    Unbuffered(std::io::Stdout),
-    Buffered(Mutex<BufWriter<std::io::Stdout>>),
-}
-impl StdOutWriter {
-    fn new(format: FormatFunction, o_buffer_capacity: &Option<usize>) -> Self {
-        match o_buffer_capacity {
-            Some(capacity) => Self {
-                format,
-                writer: OutWriter::Buffered(Mutex::new(BufWriter::with_capacity(
-                    *capacity,
-                    std::io::stdout(),
-                ))),
-            },
-            None => Self {
-                format,
-                writer: OutWriter::Unbuffered(std::io::stdout()),
-            },
-        }
-    }
-    #[inline]
-    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
-        match &self.writer {
-            OutWriter::Unbuffered(stdout) => {
-                let mut w = stdout.lock();
-                write_buffered(self.format, now, record, &mut w)
-            }
-            OutWriter::Buffered(mbuf_w) => {
-                let mut w = mbuf_w.lock().map_err(|e| poison_err("stdout", &e))?;
-                write_buffered(self.format, now, record, &mut *w)
-            }
-        }
-    }
-
-    #[inline]
-    fn flush(&self) -> std::io::Result<()> {
-        match &self.writer {
-            OutWriter::Unbuffered(stdout) => {
-                let mut w = stdout.lock();
-                w.flush()
-            }
-            OutWriter::Buffered(mbuf_w) => {
-                let mut w = mbuf_w.lock().map_err(|e| poison_err("stdout", &e))?;
-                w.flush()
-            }
-        }
-    }
-}
-
-// The `MultiWriter` writes logs to stderr or to a set of `Writer`s, and in the latter case
-// can duplicate messages to stderr.
-pub(crate) struct MultiWriter {
-    duplicate_stderr: Duplicate,
-    duplicate_stdout: Duplicate,
-    format_for_stderr: FormatFunction,
-    format_for_stdout: FormatFunction,
-    o_file_writer: Option<Box<FileLogWriter>>,
-    o_other_writer: Option<Box<dyn LogWriter>>,
-}
-
-impl MultiWriter {
-    pub(crate) fn reset_file_log_writer(
-        &self,
-        flwb: &FileLogWriterBuilder,
-    ) -> Result<(), FlexiLoggerError> {
-        self.o_file_writer
-            .as_ref()
-            .map_or(Err(FlexiLoggerError::Reset), |flw| flw.reset(flwb))
-    }
-}
-
-impl LogWriter for MultiWriter {
-    fn validate_logs(&self, expected: &[(&'static str, &'static str, &'static str)]) {
-        if let Some(ref writer) = self.o_file_writer {
-            (*writer).validate_logs(expected);
-        }
-        if let Some(ref writer) = self.o_other_writer {
-            (*writer).validate_logs(expected);
-        }
-    }
-
-    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
-        if match self.duplicate_stderr {
-            Duplicate::Error => record.level() == log::Level::Error,
-            Duplicate::Warn => record.level() <= log::Level::Warn,
-            Duplicate::Info => record.level() <= log::Level::Info,
-            Duplicate::Debug => record.level() <= log::Level::Debug,
-            Duplicate::Trace | Duplicate::All => true,
-            Duplicate::None => false,
-        } {
-            write_buffered(self.format_for_stderr, now, record, &mut std::io::stderr())?;
-        }
-
-        if match self.duplicate_stdout {
-            Duplicate::Error => record.level() == log::Level::Error,
-            Duplicate::Warn => record.level() <= log::Level::Warn,
-            Duplicate::Info => record.level() <= log::Level::Info,
-            Duplicate::Debug => record.level() <= log::Level::Debug,
-            Duplicate::Trace | Duplicate::All => true,
-            Duplicate::None => false,
-        } {
-            write_buffered(self.format_for_stdout, now, record, &mut std::io::stdout())?;
-        }
-
-        if let Some(ref writer) = self.o_file_writer {
-            writer.write(now, record)?;
-        }
-        if let Some(ref writer) = self.o_other_writer {
-            writer.write(now, record)?;
-        }
-        Ok(())
-    }
-
-    /// Provides the maximum log level that is to be written.
-    fn max_log_level(&self) -> log::LevelFilter {
-        *self
-            .o_file_writer
-            .as_ref()
-            .map(|w| w.max_log_level())
-            .iter()
-            .chain(
-                self.o_other_writer
-                    .as_ref()
-                    .map(|w| w.max_log_level())
-                    .iter(),
-            )
-            .max()
-            .unwrap()
-    }
-
-    fn flush(&self) -> std::io::Result<()> {
-        if let Some(ref writer) = self.o_file_writer {
-            writer.flush()?;
-        }
-        if let Some(ref writer) = self.o_other_writer {
-            writer.flush()?;
-        }
-
-        if let Duplicate::None = self.duplicate_stderr {
-            std::io::stderr().flush()?;
-        }
-        if let Duplicate::None = self.duplicate_stdout {
-            std::io::stdout().flush()?;
-        }
-        // maybe nicer, but doesn't work with rustc 1.41.1:
-        // if !matches!(self.duplicate_stderr, Duplicate::None) {
-        //     std::io::stderr().flush()?;
-        // }
-        // if !matches!(self.duplicate_stdout, Duplicate::None) {
-        //     std::io::stdout().flush()?;
-        // }
-        Ok(())
-    }
-
-    fn shutdown(&self) {
-        if let Some(ref writer) = self.o_file_writer {
-            writer.shutdown();
-        }
-        if let Some(ref writer) = self.o_other_writer {
-            writer.shutdown();
-        }
-    }
-}
-
-// Use a thread-local buffer for writing to stderr or stdout
-fn write_buffered(
-    format_function: FormatFunction,
-    now: &mut DeferredNow,
-    record: &Record,
-    w: &mut dyn Write,
-) -> Result<(), std::io::Error> {
-    let mut result: Result<(), std::io::Error> = Ok(());
-
-    buffer_with(|tl_buf| match tl_buf.try_borrow_mut() {
-        Ok(mut buffer) => {
-            (format_function)(&mut *buffer, now, record)
-                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
-            buffer
-                .write_all(b"\n")
-                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
-
-            result = w.write_all(&*buffer).map_err(|e| {
-                write_err(ERR_WRITING, &e);
-                e
-            });
-
-            buffer.clear();
-        }
-        Err(_e) => {
-            // We arrive here in the rare cases of recursive logging
-            // (e.g. log calls in Debug or Display implementations)
-            // we print the inner calls, in chronological order, before finally the
-            // outer most message is printed
-            let mut tmp_buf = Vec::<u8>::with_capacity(200);
-            (format_function)(&mut tmp_buf, now, record)
-                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
-            tmp_buf
-                .write_all(b"\n")
-                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
-
-            result = w.write_all(&tmp_buf).map_err(|e| {
-                write_err(ERR_WRITING, &e);
-                e
-            });
-        }
-    });
-    result
-}
-
-pub(crate) fn buffer_with<F>(f: F)
-where
-    F: FnOnce(&RefCell<Vec<u8>>),
-{
-    thread_local! {
-        static BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(200));
-    }
-    BUFFER.with(f);
-}
-
-const ERR_FORMATTING: &str = "formatting failed with ";
-const ERR_WRITING: &str = "writing failed with ";
-
-fn write_err(msg: &str, err: &std::io::Error) {
-    eprintln!("[flexi_logger] {} with {}", msg, err);
-}
+use crate::deferred_now::DeferredNow;
+use crate::filter::LogLineWriter;
+use crate::logger::Duplicate;
+use crate::writers::{FileLogWriter, FileLogWriterBuilder, LogWriter};
+use crate::{FlexiLoggerError, FormatFunction};
+use log::Record;
+use std::cell::RefCell;
+use std::io::{BufWriter, IoSlice, Write};
+use std::sync::{Mutex, RwLock};
+#[cfg(feature = "async")]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc::{sync_channel, SyncSender},
+    Arc,
+};
+
+// Writes either to stdout, or to stderr,
+// or to a file (with optional duplication to stderr),
+// or to nowhere (with optional "duplication" to stderr).
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum PrimaryWriter {
+    StdOut(StdOutWriter),
+    StdErr(StdErrWriter),
+    Multi(MultiWriter),
+}
+impl PrimaryWriter {
+    pub fn multi(
+        duplicate_stderr: Duplicate,
+        duplicate_stdout: Duplicate,
+        format_for_stderr: FormatFunction,
+        format_for_stdout: FormatFunction,
+        use_print: bool,
+        o_file_writer: Option<Box<FileLogWriter>>,
+        o_other_writers: Vec<Box<dyn LogWriter>>,
+        #[cfg(feature = "async")] async_capacity: Option<usize>,
+    ) -> Self {
+        Self::Multi(MultiWriter {
+            duplicate_stderr: RwLock::new(duplicate_stderr),
+            duplicate_stdout: RwLock::new(duplicate_stdout),
+            format_for_stderr,
+            format_for_stdout,
+            use_print,
+            o_file_writer,
+            o_other_writers,
+            #[cfg(feature = "async")]
+            o_stderr_async: async_capacity.map(|cap| DoubleBuffer::new(ConsoleSink::Stderr, cap)),
+            #[cfg(feature = "async")]
+            o_stdout_async: async_capacity.map(|cap| DoubleBuffer::new(ConsoleSink::Stdout, cap)),
+        })
+    }
+    pub fn stderr(
+        format: FormatFunction,
+        o_buffer_capacity: &Option<usize>,
+        use_print: bool,
+        #[cfg(feature = "async")] async_capacity: Option<usize>,
+    ) -> Self {
+        Self::StdErr(StdErrWriter::new(
+            format,
+            o_buffer_capacity,
+            use_print,
+            #[cfg(feature = "async")]
+            async_capacity,
+        ))
+    }
+
+    pub fn stdout(
+        format: FormatFunction,
+        o_buffer_capacity: &Option<usize>,
+        use_print: bool,
+        #[cfg(feature = "async")] async_capacity: Option<usize>,
+    ) -> Self {
+        Self::StdOut(StdOutWriter::new(
+            format,
+            o_buffer_capacity,
+            use_print,
+            #[cfg(feature = "async")]
+            async_capacity,
+        ))
+    }
+
+    // Write out a log line.
+    pub fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        match *self {
+            Self::StdErr(ref w) => w.write(now, record),
+            Self::StdOut(ref w) => w.write(now, record),
+            Self::Multi(ref w) => w.write(now, record),
+        }
+    }
+
+    // Flush any buffered records.
+    pub fn flush(&self) -> std::io::Result<()> {
+        match *self {
+            Self::StdErr(ref w) => w.flush(),
+            Self::StdOut(ref w) => w.flush(),
+            Self::Multi(ref w) => w.flush(),
+        }
+    }
+
+    pub fn validate_logs(&self, expected: &[(&'static str, &'static str, &'static str)]) {
+        if let Self::Multi(ref w) = *self {
+            w.validate_logs(expected);
+        }
+    }
+
+    // Updates how much gets echoed to stderr while the program runs. Returns
+    // `FlexiLoggerError::Reset` if this `PrimaryWriter` isn't a `Multi` (nothing to duplicate
+    // to in the first place).
+    pub fn adapt_duplication_to_stderr(&self, dup: Duplicate) -> Result<(), FlexiLoggerError> {
+        if let Self::Multi(ref w) = *self {
+            w.adapt_duplication_to_stderr(dup);
+            Ok(())
+        } else {
+            Err(FlexiLoggerError::Reset)
+        }
+    }
+
+    // Updates how much gets echoed to stdout while the program runs. Returns
+    // `FlexiLoggerError::Reset` if this `PrimaryWriter` isn't a `Multi` (nothing to duplicate
+    // to in the first place).
+    pub fn adapt_duplication_to_stdout(&self, dup: Duplicate) -> Result<(), FlexiLoggerError> {
+        if let Self::Multi(ref w) = *self {
+            w.adapt_duplication_to_stdout(dup);
+            Ok(())
+        } else {
+            Err(FlexiLoggerError::Reset)
+        }
+    }
+
+    pub fn shutdown(&self) {
+        self.flush().ok();
+        if let PrimaryWriter::Multi(writer) = self {
+            writer.shutdown();
+        }
+    }
+
+    // Closes and recreates the current output file, for use behind external log rotation
+    // (e.g. `logrotate`) that moves the file out from under the running process. No-op for
+    // `StdOut`/`StdErr`, since there's no file to reopen.
+    pub fn reopen(&self) -> Result<(), FlexiLoggerError> {
+        match self {
+            Self::StdOut(_) | Self::StdErr(_) => Ok(()),
+            Self::Multi(w) => w.reopen(),
+        }
+    }
+}
+
+impl LogLineWriter for PrimaryWriter {
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        self.write(now, record)
+    }
+}
+
+// The real sink a `DoubleBuffer`'s background thread ultimately drains into.
+#[cfg(feature = "async")]
+enum ConsoleSink {
+    Stderr,
+    Stdout,
+}
+#[cfg(feature = "async")]
+impl ConsoleSink {
+    fn write_all(&self, buf: &[u8]) -> std::io::Result<()> {
+        match self {
+            Self::Stderr => std::io::stderr().write_all(buf),
+            Self::Stdout => std::io::stdout().write_all(buf),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+enum ConsoleWriterMessage {
+    // The buffer at this index just crossed the threshold; drain it.
+    Drain(usize),
+    // Force both buffers out and ack once done, for `flush()`.
+    Flush(SyncSender<()>),
+    Shutdown,
+}
+
+// A double-buffered, background-threaded writer for stdout/stderr, mirroring the async write
+// mode `FileLogWriter` already offers for files: producers append the formatted line into
+// whichever of the two buffers is currently "active", under a `Mutex` that's only ever held
+// for the memcpy, never for the actual I/O. Once the active buffer crosses `threshold`, the
+// producer swaps the active index to the other (empty) buffer and hands the full one to the
+// background thread, which drains it with a single `write_all`. Producers never block on the
+// console, and the drain in progress never contends with the buffer producers are filling.
+#[cfg(feature = "async")]
+struct DoubleBuffer {
+    buffers: [Mutex<Vec<u8>>; 2],
+    active: AtomicUsize,
+    threshold: usize,
+    sender: SyncSender<ConsoleWriterMessage>,
+    join_handle: Mutex<Option<std::thread::JoinHandle<()>>>,
+}
+#[cfg(feature = "async")]
+impl DoubleBuffer {
+    fn new(sink: ConsoleSink, capacity: usize) -> Arc<Self> {
+        let (sender, receiver) = sync_channel(8);
+        let db = Arc::new(Self {
+            buffers: [
+                Mutex::new(Vec::with_capacity(capacity)),
+                Mutex::new(Vec::with_capacity(capacity)),
+            ],
+            active: AtomicUsize::new(0),
+            threshold: capacity,
+            sender,
+            join_handle: Mutex::new(None),
+        });
+
+        let thread_db = Arc::clone(&db);
+        let join_handle = std::thread::Builder::new()
+            .name("flexi_logger-console-writer".to_string())
+            .spawn(move || {
+                let drain = |idx: usize| {
+                    let mut buf = thread_db.buffers[idx]
+                        .lock()
+                        .unwrap(/* ok, not expected to be poisoned */);
+                    if !buf.is_empty() {
+                        sink.write_all(&buf)
+                            .unwrap_or_else(|e| write_err(ERR_WRITING, &e));
+                        buf.clear();
+                    }
+                };
+                while let Ok(msg) = receiver.recv() {
+                    match msg {
+                        ConsoleWriterMessage::Drain(idx) => drain(idx),
+                        ConsoleWriterMessage::Flush(ack) => {
+                            drain(0);
+                            drain(1);
+                            ack.send(()).ok();
+                        }
+                        ConsoleWriterMessage::Shutdown => {
+                            drain(0);
+                            drain(1);
+                            break;
+                        }
+                    }
+                }
+            })
+            .expect("failed to spawn flexi_logger-console-writer thread");
+        *db.join_handle.lock().unwrap(/* ok, not expected to be poisoned */) = Some(join_handle);
+        db
+    }
+
+    // Appends `line` into the active buffer; once that pushes it past `threshold`, swaps the
+    // active index to the other buffer and signals the background thread to drain the full one.
+    fn append(&self, line: &[u8]) {
+        let idx = self.active.load(Ordering::Acquire);
+        let full = {
+            let mut buf = self.buffers[idx]
+                .lock()
+                .unwrap(/* ok, not expected to be poisoned */);
+            buf.extend_from_slice(line);
+            buf.len() >= self.threshold
+        };
+        if full {
+            self.active.store(1 - idx, Ordering::Release);
+            self.sender.send(ConsoleWriterMessage::Drain(idx)).ok();
+        }
+    }
+
+    // Forces a swap of the active buffer and waits for the background thread to drain both
+    // buffers, so nothing is left pending once this returns.
+    fn flush(&self) {
+        let idx = self.active.load(Ordering::Acquire);
+        self.active.store(1 - idx, Ordering::Release);
+        let (ack_sender, ack_receiver) = sync_channel(1);
+        if self.sender.send(ConsoleWriterMessage::Flush(ack_sender)).is_ok() {
+            ack_receiver.recv().ok();
+        }
+    }
+}
+#[cfg(feature = "async")]
+impl Drop for DoubleBuffer {
+    // Flushes whatever is still pending and stops the background thread.
+    fn drop(&mut self) {
+        self.sender.send(ConsoleWriterMessage::Shutdown).ok();
+        if let Some(handle) = self.join_handle.lock().unwrap(/* ok, not expected to be poisoned */).take() {
+            handle.join().ok();
+        }
+    }
+}
+
+// `StdErrWriter` writes logs to stderr.
+pub(crate) struct StdErrWriter {
+    format: FormatFunction,
+    writer: ErrWriter,
+}
+enum ErrWriter {
+    Unbuffered(std::io::Stderr),
+    Buffered(Mutex<BufWriter<std::io::Stderr>>),
+    #[cfg(feature = "async")]
+    Async(Arc<DoubleBuffer>),
+    Captured,
+}
+impl StdErrWriter {
+    fn new(
+        format: FormatFunction,
+        o_buffer_capacity: &Option<usize>,
+        use_print: bool,
+        #[cfg(feature = "async")] async_capacity: Option<usize>,
+    ) -> Self {
+        if use_print {
+            return Self {
+                format,
+                writer: ErrWriter::Captured,
+            };
+        }
+        #[cfg(feature = "async")]
+        if let Some(capacity) = async_capacity {
+            return Self {
+                format,
+                writer: ErrWriter::Async(DoubleBuffer::new(ConsoleSink::Stderr, capacity)),
+            };
+        }
+        match o_buffer_capacity {
+            Some(capacity) => Self {
+                format,
+                writer: ErrWriter::Buffered(Mutex::new(BufWriter::with_capacity(
+                    *capacity,
+                    std::io::stderr(),
+                ))),
+            },
+            None => Self {
+                format,
+                writer: ErrWriter::Unbuffered(std::io::stderr()),
+            },
+        }
+    }
+    #[inline]
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        match &self.writer {
+            ErrWriter::Unbuffered(stderr) => {
+                let mut w = stderr.lock();
+                write_buffered(self.format, now, record, |buf, _recursive| w.write_all(buf))
+            }
+            ErrWriter::Buffered(mbuf_w) => {
+                let mut w = mbuf_w.lock().map_err(|e| poison_err("stderr", &e))?;
+                write_buffered(self.format, now, record, |buf, _recursive| w.write_all(buf))
+            }
+            #[cfg(feature = "async")]
+            ErrWriter::Async(db) => write_buffered(self.format, now, record, |buf, _recursive| {
+                db.append(buf);
+                Ok(())
+            }),
+            ErrWriter::Captured => write_captured(self.format, now, record, true),
+        }
+    }
+
+    #[inline]
+    fn flush(&self) -> std::io::Result<()> {
+        match &self.writer {
+            ErrWriter::Unbuffered(stderr) => {
+                let mut w = stderr.lock();
+                w.flush()
+            }
+            ErrWriter::Buffered(mbuf_w) => {
+                let mut w = mbuf_w.lock().map_err(|e| poison_err("stderr", &e))?;
+                w.flush()
+            }
+            #[cfg(feature = "async")]
+            ErrWriter::Async(db) => {
+                db.flush();
+                Ok(())
+            }
+            ErrWriter::Captured => Ok(()),
+        }
+    }
+}
+
+fn poison_err(s: &'static str, _e: &dyn std::error::Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Other, s)
+}
+
+// `StdOutWriter` writes logs to stdout.
+pub(crate) struct StdOutWriter {
+    format: FormatFunction,
+    writer: OutWriter,
+}
+enum OutWriter {
+    Unbuffered(std::io::Stdout),
+    Buffered(Mutex<BufWriter<std::io::Stdout>>),
+    #[cfg(feature = "async")]
+    Async(Arc<DoubleBuffer>),
+    Captured,
+}
+impl StdOutWriter {
+    fn new(
+        format: FormatFunction,
+        o_buffer_capacity: &Option<usize>,
+        use_print: bool,
+        #[cfg(feature = "async")] async_capacity: Option<usize>,
+    ) -> Self {
+        if use_print {
+            return Self {
+                format,
+                writer: OutWriter::Captured,
+            };
+        }
+        #[cfg(feature = "async")]
+        if let Some(capacity) = async_capacity {
+            return Self {
+                format,
+                writer: OutWriter::Async(DoubleBuffer::new(ConsoleSink::Stdout, capacity)),
+            };
+        }
+        match o_buffer_capacity {
+            Some(capacity) => Self {
+                format,
+                writer: OutWriter::Buffered(Mutex::new(BufWriter::with_capacity(
+                    *capacity,
+                    std::io::stdout(),
+                ))),
+            },
+            None => Self {
+                format,
+                writer: OutWriter::Unbuffered(std::io::stdout()),
+            },
+        }
+    }
+    #[inline]
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        match &self.writer {
+            OutWriter::Unbuffered(stdout) => {
+                let mut w = stdout.lock();
+                write_buffered(self.format, now, record, |buf, _recursive| w.write_all(buf))
+            }
+            OutWriter::Buffered(mbuf_w) => {
+                let mut w = mbuf_w.lock().map_err(|e| poison_err("stdout", &e))?;
+                write_buffered(self.format, now, record, |buf, _recursive| w.write_all(buf))
+            }
+            #[cfg(feature = "async")]
+            OutWriter::Async(db) => write_buffered(self.format, now, record, |buf, _recursive| {
+                db.append(buf);
+                Ok(())
+            }),
+            OutWriter::Captured => write_captured(self.format, now, record, false),
+        }
+    }
+
+    #[inline]
+    fn flush(&self) -> std::io::Result<()> {
+        match &self.writer {
+            OutWriter::Unbuffered(stdout) => {
+                let mut w = stdout.lock();
+                w.flush()
+            }
+            OutWriter::Buffered(mbuf_w) => {
+                let mut w = mbuf_w.lock().map_err(|e| poison_err("stdout", &e))?;
+                w.flush()
+            }
+            #[cfg(feature = "async")]
+            OutWriter::Async(db) => {
+                db.flush();
+                Ok(())
+            }
+            OutWriter::Captured => Ok(()),
+        }
+    }
+}
+
+// The `MultiWriter` writes logs to stderr or to a set of `Writer`s, and in the latter case
+// can duplicate messages to stderr.
+pub(crate) struct MultiWriter {
+    duplicate_stderr: RwLock<Duplicate>,
+    duplicate_stdout: RwLock<Duplicate>,
+    format_for_stderr: FormatFunction,
+    format_for_stdout: FormatFunction,
+    use_print: bool,
+    o_file_writer: Option<Box<FileLogWriter>>,
+    o_other_writers: Vec<Box<dyn LogWriter>>,
+    #[cfg(feature = "async")]
+    o_stderr_async: Option<Arc<DoubleBuffer>>,
+    #[cfg(feature = "async")]
+    o_stdout_async: Option<Arc<DoubleBuffer>>,
+}
+
+impl MultiWriter {
+    pub(crate) fn reset_file_log_writer(
+        &self,
+        flwb: &FileLogWriterBuilder,
+    ) -> Result<(), FlexiLoggerError> {
+        self.o_file_writer
+            .as_ref()
+            .map_or(Err(FlexiLoggerError::Reset), |flw| flw.reset(flwb))
+    }
+
+    pub(crate) fn adapt_duplication_to_stderr(&self, dup: Duplicate) {
+        if let Ok(mut guard) = self.duplicate_stderr.write() {
+            *guard = dup;
+        }
+    }
+
+    pub(crate) fn adapt_duplication_to_stdout(&self, dup: Duplicate) {
+        if let Ok(mut guard) = self.duplicate_stdout.write() {
+            *guard = dup;
+        }
+    }
+
+    // Reopens the file writer (if any) and each additional writer (if it supports it).
+    fn reopen(&self) -> Result<(), FlexiLoggerError> {
+        if let Some(ref writer) = self.o_file_writer {
+            writer.flush().map_err(FlexiLoggerError::ReopenIo)?;
+            writer.reopen()?;
+        }
+        for writer in &self.o_other_writers {
+            writer.flush().map_err(FlexiLoggerError::ReopenIo)?;
+            writer.reopen().map_err(FlexiLoggerError::ReopenIo)?;
+        }
+        Ok(())
+    }
+}
+
+impl LogWriter for MultiWriter {
+    fn validate_logs(&self, expected: &[(&'static str, &'static str, &'static str)]) {
+        if let Some(ref writer) = self.o_file_writer {
+            (*writer).validate_logs(expected);
+        }
+        for writer in &self.o_other_writers {
+            (*writer).validate_logs(expected);
+        }
+    }
+
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        if match *self.duplicate_stderr.read().map_err(|e| poison_err("duplicate_stderr", &e))? {
+            Duplicate::Error => record.level() == log::Level::Error,
+            Duplicate::Warn => record.level() <= log::Level::Warn,
+            Duplicate::Info => record.level() <= log::Level::Info,
+            Duplicate::Debug => record.level() <= log::Level::Debug,
+            Duplicate::Trace | Duplicate::All => true,
+            Duplicate::None => false,
+        } {
+            if self.use_print {
+                write_captured(self.format_for_stderr, now, record, true)?;
+            } else {
+                #[cfg(feature = "async")]
+                if let Some(ref db) = self.o_stderr_async {
+                    write_buffered(self.format_for_stderr, now, record, |buf, _recursive| {
+                        db.append(buf);
+                        Ok(())
+                    })?;
+                } else {
+                    write_buffered(self.format_for_stderr, now, record, |buf, is_recursive| {
+                        write_batched(&STDERR_BATCH, buf, is_recursive, std::io::stderr())
+                    })?;
+                }
+                #[cfg(not(feature = "async"))]
+                write_buffered(self.format_for_stderr, now, record, |buf, is_recursive| {
+                    write_batched(&STDERR_BATCH, buf, is_recursive, std::io::stderr())
+                })?;
+            }
+        }
+
+        if match *self.duplicate_stdout.read().map_err(|e| poison_err("duplicate_stdout", &e))? {
+            Duplicate::Error => record.level() == log::Level::Error,
+            Duplicate::Warn => record.level() <= log::Level::Warn,
+            Duplicate::Info => record.level() <= log::Level::Info,
+            Duplicate::Debug => record.level() <= log::Level::Debug,
+            Duplicate::Trace | Duplicate::All => true,
+            Duplicate::None => false,
+        } {
+            if self.use_print {
+                write_captured(self.format_for_stdout, now, record, false)?;
+            } else {
+                #[cfg(feature = "async")]
+                if let Some(ref db) = self.o_stdout_async {
+                    write_buffered(self.format_for_stdout, now, record, |buf, _recursive| {
+                        db.append(buf);
+                        Ok(())
+                    })?;
+                } else {
+                    write_buffered(self.format_for_stdout, now, record, |buf, is_recursive| {
+                        write_batched(&STDOUT_BATCH, buf, is_recursive, std::io::stdout())
+                    })?;
+                }
+                #[cfg(not(feature = "async"))]
+                write_buffered(self.format_for_stdout, now, record, |buf, is_recursive| {
+                    write_batched(&STDOUT_BATCH, buf, is_recursive, std::io::stdout())
+                })?;
+            }
+        }
+
+        if let Some(ref writer) = self.o_file_writer {
+            writer.write(now, record)?;
+        }
+        // Every writer in `o_other_writers` gets the record, even if an earlier one failed;
+        // only the first error is reported back, so one dead writer can't silence the rest.
+        let mut first_err = None;
+        for writer in &self.o_other_writers {
+            if let Err(e) = writer.write(now, record) {
+                if first_err.is_none() {
+                    first_err = Some(e);
+                }
+            }
+        }
+        if let Some(e) = first_err {
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Provides the maximum log level that is to be written.
+    fn max_log_level(&self) -> log::LevelFilter {
+        *self
+            .o_file_writer
+            .as_ref()
+            .map(|w| w.max_log_level())
+            .iter()
+            .chain(self.o_other_writers.iter().map(|w| w.max_log_level()))
+            .max()
+            .unwrap()
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        if let Some(ref writer) = self.o_file_writer {
+            writer.flush()?;
+        }
+        for writer in &self.o_other_writers {
+            writer.flush()?;
+        }
+
+        #[cfg(feature = "async")]
+        if let Some(ref db) = self.o_stderr_async {
+            db.flush();
+        }
+        #[cfg(feature = "async")]
+        if let Some(ref db) = self.o_stdout_async {
+            db.flush();
+        }
+
+        // `LineBatch` is thread-local, so this only drains *this* thread's pending batch; a
+        // batch filled by another logging thread still drains on its own next write once it
+        // crosses `CONSOLE_BATCH_THRESHOLD`, the same way `BufWriter`'s per-thread state would.
+        flush_batched(&STDERR_BATCH, std::io::stderr())?;
+        flush_batched(&STDOUT_BATCH, std::io::stdout())?;
+
+        if !self.use_print {
+            if let Duplicate::None = *self.duplicate_stderr.read().map_err(|e| poison_err("duplicate_stderr", &e))? {
+                std::io::stderr().flush()?;
+            }
+            if let Duplicate::None = *self.duplicate_stdout.read().map_err(|e| poison_err("duplicate_stdout", &e))? {
+                std::io::stdout().flush()?;
+            }
+        }
+        // maybe nicer, but doesn't work with rustc 1.41.1:
+        // if !matches!(self.duplicate_stderr, Duplicate::None) {
+        //     std::io::stderr().flush()?;
+        // }
+        // if !matches!(self.duplicate_stdout, Duplicate::None) {
+        //     std::io::stdout().flush()?;
+        // }
+        Ok(())
+    }
+
+    fn shutdown(&self) {
+        if let Some(ref writer) = self.o_file_writer {
+            writer.shutdown();
+        }
+        for writer in &self.o_other_writers {
+            writer.shutdown();
+        }
+    }
+}
+
+// Use a thread-local buffer for writing to stderr or stdout. `sink` is given the formatted,
+// newline-terminated line and whether it arrived via the recursive-logging fallback path below;
+// it's a closure rather than a bare `&mut dyn Write` so callers can hand it off to a
+// `DoubleBuffer::append` or a `LineBatch` just as easily as to a real `io::Write` target.
+fn write_buffered(
+    format_function: FormatFunction,
+    now: &mut DeferredNow,
+    record: &Record,
+    mut sink: impl FnMut(&[u8], bool) -> std::io::Result<()>,
+) -> Result<(), std::io::Error> {
+    let mut result: Result<(), std::io::Error> = Ok(());
+
+    buffer_with(|tl_buf| match tl_buf.try_borrow_mut() {
+        Ok(mut buffer) => {
+            (format_function)(&mut *buffer, now, record)
+                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
+            buffer
+                .write_all(b"\n")
+                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
+
+            result = sink(&buffer, false).map_err(|e| {
+                write_err(ERR_WRITING, &e);
+                e
+            });
+
+            buffer.clear();
+        }
+        Err(_e) => {
+            // We arrive here in the rare cases of recursive logging
+            // (e.g. log calls in Debug or Display implementations)
+            // we print the inner calls, in chronological order, before finally the
+            // outer most message is printed
+            let mut tmp_buf = Vec::<u8>::with_capacity(200);
+            (format_function)(&mut tmp_buf, now, record)
+                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
+            tmp_buf
+                .write_all(b"\n")
+                .unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
+
+            result = sink(&tmp_buf, true).map_err(|e| {
+                write_err(ERR_WRITING, &e);
+                e
+            });
+        }
+    });
+    result
+}
+
+// Like `write_buffered`, but goes through the `print!`/`eprint!` macros instead of an
+// `io::Write` handle, so the output passes through whatever captures those macros -- in
+// particular, `cargo test`'s per-test output capture.
+fn write_captured(
+    format_function: FormatFunction,
+    now: &mut DeferredNow,
+    record: &Record,
+    to_stderr: bool,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::<u8>::with_capacity(200);
+    (format_function)(&mut buffer, now, record).unwrap_or_else(|e| write_err(ERR_FORMATTING, &e));
+    let line = String::from_utf8_lossy(&buffer);
+    if to_stderr {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+pub(crate) fn buffer_with<F>(f: F)
+where
+    F: FnOnce(&RefCell<Vec<u8>>),
+{
+    thread_local! {
+        static BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(200));
+    }
+    BUFFER.with(f);
+}
+
+const ERR_FORMATTING: &str = "formatting failed with ";
+const ERR_WRITING: &str = "writing failed with ";
+
+fn write_err(msg: &str, err: &std::io::Error) {
+    eprintln!("[flexi_logger] {} with {}", msg, err);
+}
+
+// Flush a batch once it holds at least this many bytes across its accumulated lines.
+const CONSOLE_BATCH_THRESHOLD: usize = 8 * 1024;
+
+// A thread-local accumulation of formatted lines for `MultiWriter`'s console-duplication path,
+// batched into a single `write_vectored` call instead of one `write_all` per record. Each line
+// keeps its own heap allocation (rather than being appended into one shared buffer) so an
+// `IoSlice` can borrow it directly when the batch is drained, without an extra copy.
+struct LineBatch {
+    lines: Vec<Vec<u8>>,
+    total_bytes: usize,
+}
+impl LineBatch {
+    const fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            total_bytes: 0,
+        }
+    }
+
+    fn push(&mut self, line: &[u8]) {
+        self.total_bytes += line.len();
+        self.lines.push(line.to_vec());
+    }
+
+    // Writes out all accumulated lines with a single `write_vectored` call (looping only if the
+    // sink can't take them all at once) and clears the batch.
+    fn drain_into(&mut self, mut w: impl Write) -> std::io::Result<()> {
+        if self.lines.is_empty() {
+            return Ok(());
+        }
+        let mut slices: Vec<IoSlice> = self.lines.iter().map(|line| IoSlice::new(line)).collect();
+        let result = write_all_vectored(&mut w, &mut slices);
+        self.lines.clear();
+        self.total_bytes = 0;
+        result
+    }
+}
+
+thread_local! {
+    static STDERR_BATCH: RefCell<LineBatch> = RefCell::new(LineBatch::new());
+    static STDOUT_BATCH: RefCell<LineBatch> = RefCell::new(LineBatch::new());
+}
+
+// Appends `line` to `batch`, draining it with a single `write_vectored` call once the threshold
+// is crossed. `is_recursive` comes straight from `write_buffered`'s fallback path: the batch is
+// flushed first so the recursively-logged line still lands after everything queued ahead of it.
+fn write_batched(
+    batch: &'static std::thread::LocalKey<RefCell<LineBatch>>,
+    line: &[u8],
+    is_recursive: bool,
+    mut w: impl Write,
+) -> std::io::Result<()> {
+    if is_recursive {
+        flush_batched(batch, &mut w)?;
+        return w.write_all(line);
+    }
+    batch.with(|cell| {
+        let mut batch = cell.borrow_mut();
+        batch.push(line);
+        if batch.total_bytes >= CONSOLE_BATCH_THRESHOLD {
+            batch.drain_into(w)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+fn flush_batched(
+    batch: &'static std::thread::LocalKey<RefCell<LineBatch>>,
+    w: impl Write,
+) -> std::io::Result<()> {
+    batch.with(|cell| cell.borrow_mut().drain_into(w))
+}
+
+// Stable-Rust stand-in for the still-unstable `Write::write_all_vectored`: repeatedly calls
+// `write_vectored`, advancing past however much each call accepted, until every slice is sent.
+fn write_all_vectored(w: &mut impl Write, mut bufs: &mut [IoSlice]) -> std::io::Result<()> {
+    while !bufs.is_empty() {
+        match w.write_vectored(bufs) {
+            Ok(0) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ))
+            }
+            Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
@@ -20,6 +20,9 @@ pub struct FileLogWriterBuilder {
     o_rotation_config: Option<RotationConfig>,
     max_log_level: log::LevelFilter,
     cleanup_in_background_thread: bool,
+    cfg_watch_external_rotation: bool,
+    cfg_use_utc: bool,
+    cfg_bytes_per_sync: u64,
 }
 
 /// Methods for influencing the behavior of the [`FileLogWriter`].
@@ -36,6 +39,9 @@ impl FileLogWriterBuilder {
             format: default_format,
             max_log_level: log::LevelFilter::Trace,
             cleanup_in_background_thread: true,
+            cfg_watch_external_rotation: false,
+            cfg_use_utc: false,
+            cfg_bytes_per_sync: 0,
         }
     }
 
@@ -138,6 +144,45 @@ impl FileLogWriterBuilder {
         self
     }
 
+    /// Makes the [`FileLogWriter`] watch the directory of its active log file and reopen it
+    /// if an external tool (e.g. `logrotate`) removes or renames it out from under us.
+    ///
+    /// Without this, an externally rotated log file would leave us writing into an unlinked
+    /// inode, and nothing would ever show up at the path other tools expect to read from.
+    ///
+    /// Only available with feature `external_rotation_watch`.
+    #[cfg(feature = "external_rotation_watch")]
+    #[must_use]
+    pub fn watch_external_rotation(mut self) -> Self {
+        self.cfg_watch_external_rotation = true;
+        self
+    }
+
+    /// Formats and names rotated/named log files using UTC rather than local time.
+    ///
+    /// [`Logger::use_utc`](crate::Logger::use_utc) sets this; the timestamps that the format
+    /// functions render into the log lines are not affected by it.
+    #[must_use]
+    pub fn use_utc(mut self) -> Self {
+        self.cfg_use_utc = true;
+        self
+    }
+
+    /// Bounds how much log data can be lost on a crash without fsync-ing on every record.
+    ///
+    /// Once this many bytes have been written to the active log file since the last sync (or
+    /// since it was opened/rotated), the next write triggers `File::sync_data` and resets the
+    /// counter. The default, `0`, disables incremental sync entirely (the previous behavior:
+    /// data is only as durable as the OS page cache until the file is closed or explicitly
+    /// flushed). A small value trades some throughput for a bound on data loss; syncing on
+    /// every single record would be `bytes_per_sync(1)`, but is rarely worth the latency hit
+    /// that implies.
+    #[must_use]
+    pub fn bytes_per_sync(mut self, bytes_per_sync: u64) -> Self {
+        self.cfg_bytes_per_sync = bytes_per_sync;
+        self
+    }
+
     /// Use Windows line endings, rather than just `\n`.
     #[must_use]
     pub fn use_windows_line_ending(mut self) -> Self {
@@ -170,6 +215,19 @@ impl FileLogWriterBuilder {
         self.cfg_write_mode.buffersize()
     }
 
+    // `Some(bufsize)` if `write_mode()` was set to `FlWriteMode::BufferAsync`, `None` otherwise.
+    // Used by `Logger::build` to decide whether the stdout/stderr `PrimaryWriter`s should also
+    // use the double-buffered async console writer instead of a plain `BufWriter`.
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub(crate) fn async_buffersize(&self) -> Option<usize> {
+        if let FlWriteMode::BufferAsync(bufsize, _, _) = self.cfg_write_mode {
+            Some(bufsize)
+        } else {
+            None
+        }
+    }
+
     /// Produces the `FileLogWriter`.
     ///
     /// # Errors
@@ -210,6 +268,9 @@ impl FileLogWriterBuilder {
                 write_mode: self.cfg_write_mode,
                 file_spec: self.file_spec.clone(),
                 o_create_symlink: self.cfg_o_create_symlink.as_ref().map(Clone::clone),
+                watch_external_rotation: self.cfg_watch_external_rotation,
+                use_utc: self.cfg_use_utc,
+                bytes_per_sync: self.cfg_bytes_per_sync,
             },
             self.o_rotation_config.as_ref().map(Clone::clone),
             cleanup_in_background_thread,
@@ -292,6 +353,21 @@ pub enum FlWriteMode {
     ///
     /// Only available with feature `async`.
     BufferAsync(usize, usize, usize),
+
+    #[cfg(feature = "compress")]
+    /// Writes the active (`_rCURRENT`) log file as a gzip stream, with the given internal
+    /// write buffer size, instead of writing plain text and compressing it later during
+    /// cleanup. Rotated files produced in this mode get a `.gz` suffix.
+    ///
+    /// Only available with feature `compress`.
+    DirectCompress(usize),
+
+    /// Sends log messages through a bounded channel (with the given queue size) to a
+    /// dedicated writer thread, which owns the file, performs rotation and writes the
+    /// message, so that `write_buffer()` never blocks the logging call on disk I/O.
+    ///
+    /// The `OverflowPolicy` decides what happens when the queue is full.
+    BackgroundThread(usize, OverflowPolicy),
 }
 impl FlWriteMode {
     #[must_use]
@@ -301,6 +377,22 @@ impl FlWriteMode {
             Self::Buffer(bufsize) => Some(*bufsize),
             #[cfg(feature = "async")]
             Self::BufferAsync(bufsize, _poolsize, _elementsize) => Some(*bufsize),
+            #[cfg(feature = "compress")]
+            Self::DirectCompress(bufsize) => Some(*bufsize),
+            Self::BackgroundThread(_, _) => None,
         }
     }
 }
+
+/// What the [`FileLogWriter`]'s writer thread should do when its queue is full and another
+/// log message arrives, for [`FlWriteMode::BackgroundThread`]
+/// (see [`WriteMode::BackgroundThread`](crate::WriteMode::BackgroundThread)).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Block the logging call until the writer thread drains the queue.
+    Block,
+    /// Drop the oldest queued message to make room for the new one.
+    DropOldest,
+    /// Drop the incoming message and leave the queue as is.
+    DropMessage,
+}
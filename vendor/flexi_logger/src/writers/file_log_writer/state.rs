@@ -1,13 +1,15 @@
 use crate::FileSpec;
 use crate::{Age, Cleanup, Criterion, FlexiLoggerError, Naming};
-use chrono::{DateTime, Datelike, Local, Timelike};
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
 use std::cmp::max;
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::collections::VecDeque;
 use std::ops::Add;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
 
-use super::{Config, RotationConfig};
+use super::{Config, FlWriteMode, OverflowPolicy, RotationConfig};
 
 const CURRENT_INFIX: &str = "_rCURRENT";
 fn number_infix(idx: u32) -> String {
@@ -30,6 +32,14 @@ enum IdxState {
 enum NamingState {
     CreatedAt,
     IdxState(IdxState),
+    // Naming::TimestampsAndNumbers: `<basename>_r<period-timestamp>_r<idx>`. Unlike
+    // NamingState::CreatedAt, collisions within the same second are resolved by scanning
+    // for the highest existing index for that timestamp, not by a `.restart-N` suffix.
+    TimestampAndIdx,
+    // Naming::Daily: `<basename>_<year>_<month>_<day>`, one file per calendar day. Meant to
+    // be combined with `Criterion::Age(Age::Day)`, so the file is rolled exactly once the
+    // local date advances past midnight.
+    DailyDate,
 }
 
 #[derive(Debug)]
@@ -56,7 +66,89 @@ struct RotationState {
     created_at: DateTime<Local>,
     cleanup: Cleanup,
     o_cleanup_thread_handle: Option<CleanupThreadHandle>,
+    o_external_watch: Option<ExternalWatchHandle>,
 }
+
+// Watches the directory of the active log file so that, if an external tool (`logrotate`,
+// an operator, ...) removes or renames it out from under us, we notice and reopen a fresh
+// file instead of silently writing into an unlinked inode.
+#[cfg(feature = "external_rotation_watch")]
+struct ExternalWatchHandle {
+    // Set by the watcher thread when it sees the active file disappear; cleared, and acted
+    // on, by the next `write_buffer` call.
+    reopen_needed: Arc<std::sync::atomic::AtomicBool>,
+    // Set around our own rotations, so the watcher can tell "file went away because we
+    // rotated it" apart from "file went away because something else touched it".
+    internal_rotation: Arc<std::sync::atomic::AtomicBool>,
+    // Kept alive only to keep the notify watcher (and its background thread) running; never
+    // read.
+    _watcher_thread: std::thread::JoinHandle<()>,
+}
+#[cfg(feature = "external_rotation_watch")]
+impl std::fmt::Debug for ExternalWatchHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("ExternalWatchHandle(<external-rotation-watcher>)")
+    }
+}
+#[cfg(feature = "external_rotation_watch")]
+impl ExternalWatchHandle {
+    fn spawn(current_path: PathBuf) -> Result<Self, FlexiLoggerError> {
+        let reopen_needed = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let internal_rotation = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let debouncing_delay = std::time::Duration::from_millis(1000);
+        let mut watcher = notify::watcher(tx, debouncing_delay)
+            .map_err(|_| FlexiLoggerError::OutputCleanupThread(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "failed to set up the external-rotation watcher",
+            )))?;
+        if let Some(dir) = current_path.parent() {
+            notify::Watcher::watch(&mut watcher, dir, notify::RecursiveMode::NonRecursive)
+                .map_err(|_| FlexiLoggerError::OutputCleanupThread(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "failed to watch the log file's directory",
+                )))?;
+        }
+
+        let thread_reopen_needed = Arc::clone(&reopen_needed);
+        let thread_internal_rotation = Arc::clone(&internal_rotation);
+        let join_handle = std::thread::Builder::new()
+            .name("flexi_logger-external-rotation-watcher".to_string())
+            .stack_size(128 * 1024)
+            .spawn(move || {
+                let _anchor_for_watcher = watcher; // keep it alive!
+                loop {
+                    match rx.recv() {
+                        Ok(
+                            notify::DebouncedEvent::Remove(ref path)
+                            | notify::DebouncedEvent::Rename(ref path, _),
+                        ) if *path == current_path => {
+                            if !thread_internal_rotation.load(std::sync::atomic::Ordering::SeqCst) {
+                                thread_reopen_needed
+                                    .store(true, std::sync::atomic::Ordering::SeqCst);
+                            }
+                        }
+                        Ok(_event) => {}
+                        Err(_) => break,
+                    }
+                }
+            })
+            .map_err(FlexiLoggerError::OutputCleanupThread)?;
+
+        Ok(Self {
+            reopen_needed,
+            internal_rotation,
+            _watcher_thread: join_handle,
+        })
+    }
+}
+
+// Without the feature, `RotationState` still carries the field (so its shape doesn't change
+// across builds), it just can never hold a real watcher.
+#[cfg(not(feature = "external_rotation_watch"))]
+#[derive(Debug)]
+struct ExternalWatchHandle;
 impl RotationState {
     fn size_rotation_necessary(max_size: u64, current_size: u64) -> bool {
         current_size > max_size
@@ -138,7 +230,11 @@ fn try_roll_state_from_criterion(
 
 enum Inner {
     Initial(Option<RotationConfig>, bool),
-    Active(Option<RotationState>, Box<dyn Write + Send>),
+    Active(Option<RotationState>, Output),
+    // `FlWriteMode::BackgroundThread`: the writer thread owns the real `Inner::Active`
+    // state (wrapped in its own `State`, reached via `shared`) and does the file I/O,
+    // rotation, and cleanup; `write_buffer()` here just enqueues the bytes.
+    Background(BackgroundWriterHandle),
 }
 impl std::fmt::Debug for Inner {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -147,13 +243,229 @@ impl std::fmt::Debug for Inner {
             Self::Active(o_rot, _) => {
                 f.write_fmt(format_args!("Active({:?}, <some-writer>) ", o_rot,))
             }
+            Self::Background(_) => f.write_str("Background(<writer-thread>) "),
+        }
+    }
+}
+
+// What the writer thread should do with a message.
+enum MessageToWriterThread {
+    Write(Vec<u8>),
+    // Carries a one-shot sender so the caller can block until the flush, and everything
+    // queued ahead of it, has actually happened.
+    Flush(std::sync::mpsc::Sender<()>),
+    Shutdown,
+}
+
+// A bounded, blocking queue that additionally supports the three `OverflowPolicy` behaviors.
+// `std::sync::mpsc::SyncSender` cannot implement `DropOldest` (it offers no way to evict an
+// already-queued message), so we roll our own with a `Mutex<VecDeque<_>>` and two `Condvar`s.
+struct BoundedQueue {
+    inner: Mutex<VecDeque<MessageToWriterThread>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+impl BoundedQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(VecDeque::with_capacity(capacity)),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn push(&self, message: MessageToWriterThread, on_full: OverflowPolicy) {
+        let mut queue = self.inner.lock().unwrap(/*ok*/);
+        if queue.len() >= self.capacity {
+            match on_full {
+                OverflowPolicy::Block => {
+                    while queue.len() >= self.capacity {
+                        queue = self.not_full.wait(queue).unwrap(/*ok*/);
+                    }
+                }
+                OverflowPolicy::DropOldest => {
+                    queue.pop_front();
+                }
+                OverflowPolicy::DropMessage => return,
+            }
+        }
+        queue.push_back(message);
+        self.not_empty.notify_one();
+    }
+
+    fn pop(&self) -> MessageToWriterThread {
+        let mut queue = self.inner.lock().unwrap(/*ok*/);
+        loop {
+            if let Some(message) = queue.pop_front() {
+                self.not_full.notify_one();
+                return message;
+            }
+            queue = self.not_empty.wait(queue).unwrap(/*ok*/);
+        }
+    }
+}
+
+// Owns the channel to, and the join handle of, the dedicated writer thread spawned for
+// `FlWriteMode::BackgroundThread`.
+struct BackgroundWriterHandle {
+    queue: Arc<BoundedQueue>,
+    on_full: OverflowPolicy,
+    // The writer thread's own `State`, running the usual synchronous write/rotation/cleanup
+    // path. Shared so that read-mostly calls (`current_filename`, `reopen`, ...) can still
+    // be served without inventing a second control channel for each of them.
+    shared: Arc<Mutex<State>>,
+    join_handle: Option<std::thread::JoinHandle<()>>,
+}
+impl BackgroundWriterHandle {
+    fn spawn(
+        config: Config,
+        o_rotation_config: Option<RotationConfig>,
+        cleanup_in_background_thread: bool,
+        queue_size: usize,
+        on_full: OverflowPolicy,
+    ) -> Result<Self, FlexiLoggerError> {
+        let inner_config = Config {
+            print_message: config.print_message,
+            append: config.append,
+            line_ending: config.line_ending,
+            write_mode: FlWriteMode::DontBuffer,
+            file_spec: config.file_spec.clone(),
+            o_create_symlink: config.o_create_symlink.clone(),
+            watch_external_rotation: config.watch_external_rotation,
+            use_utc: config.use_utc,
+            bytes_per_sync: config.bytes_per_sync,
+        };
+        let shared = Arc::new(Mutex::new(State::try_new(
+            inner_config,
+            o_rotation_config,
+            cleanup_in_background_thread,
+        )?));
+        let queue = Arc::new(BoundedQueue::new(queue_size));
+
+        let thread_shared = Arc::clone(&shared);
+        let thread_queue = Arc::clone(&queue);
+        let join_handle = std::thread::Builder::new()
+            .name("flexi_logger-writer".to_string())
+            .spawn(move || loop {
+                match thread_queue.pop() {
+                    MessageToWriterThread::Write(buf) => {
+                        if let Ok(mut state) = thread_shared.lock() {
+                            state.write_buffer(&buf).unwrap_or_else(|e| {
+                                eprintln!("[flexi_logger] writing failed with {}", e);
+                            });
+                        }
+                    }
+                    MessageToWriterThread::Flush(ack) => {
+                        if let Ok(mut state) = thread_shared.lock() {
+                            state.flush().ok();
+                        }
+                        ack.send(()).ok();
+                    }
+                    MessageToWriterThread::Shutdown => {
+                        if let Ok(mut state) = thread_shared.lock() {
+                            state.shutdown();
+                        }
+                        break;
+                    }
+                }
+            })
+            .map_err(FlexiLoggerError::OutputCleanupThread)?;
+
+        Ok(Self {
+            queue,
+            on_full,
+            shared,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    fn enqueue(&self, buf: Vec<u8>) {
+        self.queue
+            .push(MessageToWriterThread::Write(buf), self.on_full);
+    }
+}
+
+// The actual sink a `FileLogWriter` writes into. Usually this is just the (possibly buffered)
+// file, but with `FlWriteMode::DirectCompress` it is a streaming gzip encoder writing directly
+// into the `_rCURRENT` file, so the hot file is compressed from the start.
+enum OutputSink {
+    Plain(Box<dyn Write + Send>),
+    #[cfg(feature = "compress")]
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+}
+impl Write for OutputSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(w) => w.write(buf),
+            #[cfg(feature = "compress")]
+            Self::Gzip(enc) => enc.write(buf),
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(w) => w.flush(),
+            #[cfg(feature = "compress")]
+            Self::Gzip(enc) => enc.flush(),
+        }
+    }
+}
+
+// Wraps `OutputSink` with the incremental-fsync bookkeeping for `Config::bytes_per_sync`.
+struct Output {
+    sink: OutputSink,
+    // A separate handle onto the same file, used only for `sync_data()`; since `sink` may
+    // buffer internally (`BufWriter`, the gzip encoder), `note_write` flushes `sink` itself
+    // before syncing so the fsync actually covers the bytes it's accounting for.
+    sync_file: File,
+    bytes_per_sync: u64,
+    bytes_since_sync: u64,
+}
+impl Output {
+    // Flushes and, for the gzip case, writes the final gzip footer. Must be called before
+    // the underlying file is renamed away (rotation) or reopened.
+    fn finish(self) -> std::io::Result<()> {
+        match self.sink {
+            OutputSink::Plain(mut w) => w.flush(),
+            #[cfg(feature = "compress")]
+            OutputSink::Gzip(enc) => enc.finish().map(|_file| ()),
+        }
+    }
+
+    // Called after every successful write with the number of bytes just written. Once
+    // `bytes_since_sync` crosses `bytes_per_sync`, flushes `sink` and calls `sync_data` on the
+    // underlying file, then resets the counter. A `bytes_per_sync` of `0` disables this and
+    // keeps the previous behavior of never syncing explicitly.
+    fn note_write(&mut self, n: u64) -> std::io::Result<()> {
+        if self.bytes_per_sync == 0 {
+            return Ok(());
+        }
+        self.bytes_since_sync += n;
+        if self.bytes_since_sync >= self.bytes_per_sync {
+            self.sink.flush()?;
+            self.sync_file.sync_data()?;
+            self.bytes_since_sync = 0;
         }
+        Ok(())
+    }
+}
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.sink.write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.sink.flush()
     }
 }
 
 // The mutable state of a FileLogWriter.
 pub(crate) struct State {
     config: Config,
+    // kept around (in addition to `inner`) so that `switch_file_spec()` can rebuild
+    // a fresh `Inner::Initial` without asking the caller to repeat the rotation setup.
+    o_rotation_config: Option<RotationConfig>,
+    cleanup_in_background_thread: bool,
     inner: Inner,
 }
 impl State {
@@ -162,9 +474,36 @@ impl State {
         o_rotation_config: Option<RotationConfig>,
         cleanup_in_background_thread: bool,
     ) -> Result<Self, FlexiLoggerError> {
+        if let FlWriteMode::BackgroundThread(queue_size, on_full) = config.write_mode {
+            let handle = BackgroundWriterHandle::spawn(
+                Config {
+                    print_message: config.print_message,
+                    append: config.append,
+                    line_ending: config.line_ending,
+                    write_mode: config.write_mode,
+                    file_spec: config.file_spec.clone(),
+                    o_create_symlink: config.o_create_symlink.clone(),
+                    watch_external_rotation: config.watch_external_rotation,
+                    use_utc: config.use_utc,
+                    bytes_per_sync: config.bytes_per_sync,
+                },
+                o_rotation_config.clone(),
+                cleanup_in_background_thread,
+                queue_size,
+                on_full,
+            )?;
+            return Ok(Self {
+                config,
+                inner: Inner::Background(handle),
+                o_rotation_config,
+                cleanup_in_background_thread,
+            });
+        }
         Ok(Self {
             config,
-            inner: Inner::Initial(o_rotation_config, cleanup_in_background_thread),
+            inner: Inner::Initial(o_rotation_config.clone(), cleanup_in_background_thread),
+            o_rotation_config,
+            cleanup_in_background_thread,
         })
     }
 
@@ -184,11 +523,24 @@ impl State {
                                     &get_creation_date(
                                         &self.config.file_spec.as_pathbuf(Some(CURRENT_INFIX)),
                                     ),
+                                    DATED_TIMESTAMP_FORMAT,
                                     &self.config,
                                 )?;
                             }
                             NamingState::CreatedAt
                         }
+                        Naming::Daily => {
+                            if !self.config.append {
+                                rotate_output_file_to_date(
+                                    &get_creation_date(
+                                        &self.config.file_spec.as_pathbuf(Some(CURRENT_INFIX)),
+                                    ),
+                                    DATED_DAY_FORMAT,
+                                    &self.config,
+                                )?;
+                            }
+                            NamingState::DailyDate
+                        }
                         Naming::Numbers => {
                             let mut rotation_state = get_highest_rotate_idx(&self.config.file_spec);
                             if !self.config.append {
@@ -197,6 +549,17 @@ impl State {
                             }
                             NamingState::IdxState(rotation_state)
                         }
+                        Naming::TimestampsAndNumbers => {
+                            if !self.config.append {
+                                rotate_output_file_to_timestamp_and_idx(
+                                    &get_creation_date(
+                                        &self.config.file_spec.as_pathbuf(Some(CURRENT_INFIX)),
+                                    ),
+                                    &self.config,
+                                )?;
+                            }
+                            NamingState::TimestampAndIdx
+                        }
                     };
                     let (log_file, created_at, p_path) = open_log_file(&self.config, true)?;
 
@@ -235,6 +598,16 @@ impl State {
                             });
                         }
                     }
+
+                    #[cfg(feature = "external_rotation_watch")]
+                    let o_external_watch = if self.config.watch_external_rotation {
+                        ExternalWatchHandle::spawn(p_path.clone()).ok()
+                    } else {
+                        None
+                    };
+                    #[cfg(not(feature = "external_rotation_watch"))]
+                    let o_external_watch = None;
+
                     self.inner = Inner::Active(
                         Some(RotationState {
                             naming_state,
@@ -242,6 +615,7 @@ impl State {
                             created_at,
                             cleanup: rotate_config.cleanup,
                             o_cleanup_thread_handle,
+                            o_external_watch,
                         }),
                         log_file,
                     );
@@ -256,6 +630,14 @@ impl State {
     }
 
     pub fn flush(&mut self) -> std::io::Result<()> {
+        if let Inner::Background(ref handle) = self.inner {
+            let (ack_sender, ack_receiver) = std::sync::mpsc::channel();
+            handle
+                .queue
+                .push(MessageToWriterThread::Flush(ack_sender), OverflowPolicy::Block);
+            ack_receiver.recv().ok();
+            return Ok(());
+        }
         if let Inner::Active(_, ref mut file) = self.inner {
             file.flush()
         } else {
@@ -268,38 +650,108 @@ impl State {
     // before writing into `_rCURRENT` goes on.
     #[inline]
     fn mount_next_linewriter_if_necessary(&mut self) -> Result<(), FlexiLoggerError> {
+        let rotation_necessary = matches!(
+            &self.inner,
+            Inner::Active(Some(rotation_state), _) if rotation_state.rotation_necessary()
+        );
+        if rotation_necessary {
+            self.force_rotate()?;
+        }
+        Ok(())
+    }
+
+    // Unconditionally rolls the current `_rCURRENT` file, independent of `RollState`. Shared
+    // by `mount_next_linewriter_if_necessary()`, once its criterion fires, and by the public
+    // `rotate()`, which lets callers trigger a rotation on demand (e.g. from a `SIGHUP`
+    // handler or a scheduled job).
+    fn force_rotate(&mut self) -> Result<(), FlexiLoggerError> {
         if let Inner::Active(Some(ref mut rotation_state), ref mut file) = self.inner {
-            if rotation_state.rotation_necessary() {
-                match rotation_state.naming_state {
-                    NamingState::CreatedAt => {
-                        rotate_output_file_to_date(&rotation_state.created_at, &self.config)?;
-                    }
-                    NamingState::IdxState(ref mut idx_state) => {
-                        *idx_state = rotate_output_file_to_idx(*idx_state, &self.config)?;
-                    }
-                }
+            #[cfg(feature = "external_rotation_watch")]
+            if let Some(ref watch) = rotation_state.o_external_watch {
+                // The rename below is us, not an external tool; tell the watcher to ignore it.
+                watch
+                    .internal_rotation
+                    .store(true, std::sync::atomic::Ordering::SeqCst);
+            }
 
-                let (line_writer, created_at, _) = open_log_file(&self.config, true)?;
-                *file = line_writer;
-                rotation_state.created_at = created_at;
-                if let RollState::Size(_, ref mut current_size)
-                | RollState::AgeOrSize(_, _, ref mut current_size) = rotation_state.roll_state
-                {
-                    *current_size = 0;
+            match rotation_state.naming_state {
+                NamingState::CreatedAt => {
+                    rotate_output_file_to_date(
+                        &rotation_state.created_at,
+                        DATED_TIMESTAMP_FORMAT,
+                        &self.config,
+                    )?;
+                }
+                NamingState::DailyDate => {
+                    rotate_output_file_to_date(
+                        &rotation_state.created_at,
+                        DATED_DAY_FORMAT,
+                        &self.config,
+                    )?;
+                }
+                NamingState::IdxState(ref mut idx_state) => {
+                    *idx_state = rotate_output_file_to_idx(*idx_state, &self.config)?;
+                }
+                NamingState::TimestampAndIdx => {
+                    rotate_output_file_to_timestamp_and_idx(
+                        &rotation_state.created_at,
+                        &self.config,
+                    )?;
                 }
+            }
+
+            let (line_writer, created_at, _) = open_log_file(&self.config, true)?;
+            std::mem::replace(file, line_writer).finish()?;
+            rotation_state.created_at = created_at;
+            if let RollState::Size(_, ref mut current_size)
+            | RollState::AgeOrSize(_, _, ref mut current_size) = rotation_state.roll_state
+            {
+                *current_size = 0;
+            }
+
+            remove_or_compress_too_old_logfiles(
+                &rotation_state.o_cleanup_thread_handle,
+                &rotation_state.cleanup,
+                &self.config.file_spec,
+            )?;
 
-                remove_or_compress_too_old_logfiles(
-                    &rotation_state.o_cleanup_thread_handle,
-                    &rotation_state.cleanup,
-                    &self.config.file_spec,
-                )?;
+            #[cfg(feature = "external_rotation_watch")]
+            if let Some(ref watch) = rotation_state.o_external_watch {
+                watch
+                    .reopen_needed
+                    .store(false, std::sync::atomic::Ordering::SeqCst);
+                watch
+                    .internal_rotation
+                    .store(false, std::sync::atomic::Ordering::SeqCst);
             }
         }
 
         Ok(())
     }
 
+    // Forces an immediate rotation of the current log file, regardless of whether the
+    // configured `Criterion` (size/age) would trigger one. No-op if no rotation was
+    // configured for this writer. Lets callers wire rotation to an external trigger instead
+    // of relying purely on count/size/age criteria.
+    pub fn rotate(&mut self) -> Result<(), FlexiLoggerError> {
+        if let Inner::Background(ref handle) = self.inner {
+            return handle
+                .shared
+                .lock()
+                .map_err(|_| FlexiLoggerError::Poison)?
+                .rotate();
+        }
+        if let Inner::Initial(_, _) = self.inner {
+            self.initialize()?;
+        }
+        self.force_rotate()
+    }
+
     pub fn write_buffer(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        if let Inner::Background(ref handle) = self.inner {
+            handle.enqueue(buf.to_vec());
+            return Ok(());
+        }
         if let Inner::Initial(_, _) = self.inner {
             self.initialize()?;
         }
@@ -309,8 +761,12 @@ impl State {
                 eprintln!("[flexi_logger] opening file failed with {}", e);
             });
 
+        #[cfg(feature = "external_rotation_watch")]
+        self.reopen_if_externally_rotated();
+
         if let Inner::Active(ref mut o_rotation_state, ref mut log_file) = self.inner {
             log_file.write_all(buf)?;
+            log_file.note_write(buf.len() as u64)?;
             if let Some(ref mut rotation_state) = o_rotation_state {
                 if let RollState::Size(_, ref mut current_size)
                 | RollState::AgeOrSize(_, _, ref mut current_size) = rotation_state.roll_state
@@ -323,6 +779,13 @@ impl State {
     }
 
     pub fn current_filename(&self) -> PathBuf {
+        if let Inner::Background(ref handle) = self.inner {
+            return handle
+                .shared
+                .lock()
+                .map(|state| state.current_filename())
+                .unwrap_or_else(|_| self.config.file_spec.as_pathbuf(None));
+        }
         let o_infix = match &self.inner {
             Inner::Initial(o_rotation_config, _) => {
                 if o_rotation_config.is_some() {
@@ -338,11 +801,20 @@ impl State {
                     None
                 }
             }
+            Inner::Background(_) => unreachable!("handled above"),
         };
         self.config.file_spec.as_pathbuf(o_infix)
     }
 
     pub fn validate_logs(&mut self, expected: &[(&'static str, &'static str, &'static str)]) {
+        if let Inner::Background(ref handle) = self.inner {
+            handle
+                .shared
+                .lock()
+                .unwrap(/*ok*/)
+                .validate_logs(expected);
+            return;
+        }
         if let Inner::Initial(_, _) = self.inner {
             self.initialize().unwrap();
         }
@@ -373,13 +845,135 @@ impl State {
     }
 
     pub fn shutdown(&mut self) {
-        if let Inner::Active(ref mut o_rotation_state, ref mut writer) = self.inner {
-            if let Some(ref mut rotation_state) = o_rotation_state {
-                rotation_state.shutdown();
+        match &mut self.inner {
+            Inner::Active(ref mut o_rotation_state, ref mut writer) => {
+                if let Some(ref mut rotation_state) = o_rotation_state {
+                    rotation_state.shutdown();
+                }
+                writer.flush().ok();
             }
-            writer.flush().ok();
+            Inner::Background(handle) => {
+                handle
+                    .queue
+                    .push(MessageToWriterThread::Shutdown, OverflowPolicy::Block);
+                if let Some(join_handle) = handle.join_handle.take() {
+                    join_handle.join().ok();
+                }
+            }
+            Inner::Initial(_, _) => {}
         }
     }
+
+    // Returns the sorted paths of all rotated log files (and their compressed variants),
+    // plus the currently active `_rCURRENT` file, if it exists.
+    pub fn existing_log_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> =
+            list_of_log_and_compressed_files(&self.config.file_spec).collect();
+        let current = self.config.file_spec.as_pathbuf(Some(CURRENT_INFIX));
+        if current.exists() {
+            files.push(current);
+        }
+        files.sort();
+        files
+    }
+
+    // Checked on every `write_buffer()` call: if the external-rotation watcher noticed that
+    // our active file got removed or renamed out from under us, reopen a fresh file at the
+    // same configured path, the same way `reopen()` does for an explicit `SIGHUP`.
+    #[cfg(feature = "external_rotation_watch")]
+    fn reopen_if_externally_rotated(&mut self) {
+        let reopen_needed = matches!(
+            &self.inner,
+            Inner::Active(Some(rotation_state), _)
+                if rotation_state.o_external_watch.as_ref().map_or(false, |watch| {
+                    watch.reopen_needed.load(std::sync::atomic::Ordering::SeqCst)
+                })
+        );
+        if reopen_needed {
+            self.reopen().unwrap_or_else(|e| {
+                eprintln!(
+                    "[flexi_logger] reopening externally rotated file failed with {}",
+                    e
+                );
+            });
+            if let Inner::Active(Some(ref rotation_state), _) = self.inner {
+                if let Some(ref watch) = rotation_state.o_external_watch {
+                    watch
+                        .reopen_needed
+                        .store(false, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+        }
+    }
+
+    // Closes the currently open file and re-opens a file at the same configured path,
+    // in append mode. Used to recover after an external tool (logrotate, SIGHUP, ...)
+    // has renamed or removed the file we were writing to.
+    pub fn reopen(&mut self) -> Result<(), FlexiLoggerError> {
+        if let Inner::Background(ref handle) = self.inner {
+            return handle
+                .shared
+                .lock()
+                .map_err(|_| FlexiLoggerError::Poison)?
+                .reopen();
+        }
+        if let Inner::Active(ref o_rotation_state, ref mut file) = self.inner {
+            let append_config = Config {
+                print_message: self.config.print_message,
+                append: true,
+                line_ending: self.config.line_ending,
+                write_mode: self.config.write_mode,
+                file_spec: self.config.file_spec.clone(),
+                o_create_symlink: self.config.o_create_symlink.clone(),
+                watch_external_rotation: self.config.watch_external_rotation,
+                use_utc: self.config.use_utc,
+                bytes_per_sync: self.config.bytes_per_sync,
+            };
+            let (new_file, _created_at, _p_path) =
+                open_log_file(&append_config, o_rotation_state.is_some())
+                    .map_err(FlexiLoggerError::ReopenIo)?;
+            std::mem::replace(file, new_file)
+                .finish()
+                .map_err(FlexiLoggerError::ReopenIo)?;
+        }
+        Ok(())
+    }
+
+    // Atomically redirects output to a different `FileSpec`, preserving the rotation
+    // config, the append flag and the line-ending setting. The currently open file is
+    // flushed and closed; the next `write_buffer()` call lazily opens the new one.
+    //
+    // Note: with the synchronous write modes this takes effect immediately, because
+    // `write_buffer` re-checks `self.inner` on every call; an async write mode would need
+    // this forwarded as a control message to its output thread instead.
+    pub fn switch_file_spec(&mut self, new_file_spec: FileSpec) -> Result<(), FlexiLoggerError> {
+        if let Inner::Background(ref handle) = self.inner {
+            handle
+                .shared
+                .lock()
+                .map_err(|_| FlexiLoggerError::Poison)?
+                .switch_file_spec(new_file_spec.clone())?;
+            self.config.file_spec = new_file_spec;
+            return Ok(());
+        }
+        self.shutdown();
+        self.config = Config {
+            print_message: self.config.print_message,
+            append: self.config.append,
+            line_ending: self.config.line_ending,
+            write_mode: self.config.write_mode,
+            file_spec: new_file_spec,
+            o_create_symlink: self.config.o_create_symlink.clone(),
+            watch_external_rotation: self.config.watch_external_rotation,
+            use_utc: self.config.use_utc,
+            bytes_per_sync: self.config.bytes_per_sync,
+        };
+        self.inner = Inner::Initial(
+            self.o_rotation_config.clone(),
+            self.cleanup_in_background_thread,
+        );
+        Ok(())
+    }
 }
 
 impl std::fmt::Debug for State {
@@ -391,17 +985,16 @@ impl std::fmt::Debug for State {
     }
 }
 
-#[allow(clippy::type_complexity)]
 fn open_log_file(
     config: &Config,
     with_rotation: bool,
-) -> Result<(Box<dyn Write + Send>, DateTime<Local>, PathBuf), std::io::Error> {
+) -> Result<(Output, DateTime<Local>, PathBuf), std::io::Error> {
     let o_infix = if with_rotation {
         Some(CURRENT_INFIX)
     } else {
         None
     };
-    let p_path = config.file_spec.as_pathbuf(o_infix);
+    let p_path = with_direct_compress_suffix(config.file_spec.as_pathbuf(o_infix), config);
     if config.print_message {
         println!("Log is written to {}", &p_path.display());
     }
@@ -415,6 +1008,23 @@ fn open_log_file(
         .append(config.append)
         .truncate(!config.append)
         .open(&p_path)?;
+    let sync_file = log_file.try_clone()?;
+
+    #[cfg(feature = "compress")]
+    if let super::FlWriteMode::DirectCompress(bufsize) = config.write_mode {
+        let buffered = BufWriter::with_capacity(bufsize, log_file);
+        let encoder = flate2::write::GzEncoder::new(buffered, flate2::Compression::fast());
+        return Ok((
+            Output {
+                sink: OutputSink::Gzip(encoder),
+                sync_file,
+                bytes_per_sync: config.bytes_per_sync,
+                bytes_since_sync: 0,
+            },
+            get_creation_date(&p_path),
+            p_path,
+        ));
+    }
 
     #[allow(clippy::option_if_let_else)]
     let w: Box<dyn Write + Send> = if let Some(capacity) = config.write_mode.buffersize() {
@@ -422,7 +1032,16 @@ fn open_log_file(
     } else {
         Box::new(log_file)
     };
-    Ok((w, get_creation_date(&p_path), p_path))
+    Ok((
+        Output {
+            sink: OutputSink::Plain(w),
+            sync_file,
+            bytes_per_sync: config.bytes_per_sync,
+            bytes_since_sync: 0,
+        },
+        get_creation_date(&p_path),
+        p_path,
+    ))
 }
 
 fn get_highest_rotate_idx(file_spec: &FileSpec) -> IdxState {
@@ -449,7 +1068,10 @@ fn list_of_log_and_compressed_files(
     file_spec: &FileSpec,
 ) -> std::iter::Chain<
     std::iter::Chain<
-        std::vec::IntoIter<std::path::PathBuf>,
+        std::iter::Chain<
+            std::vec::IntoIter<std::path::PathBuf>,
+            std::vec::IntoIter<std::path::PathBuf>,
+        >,
         std::vec::IntoIter<std::path::PathBuf>,
     >,
     std::vec::IntoIter<std::path::PathBuf>,
@@ -457,12 +1079,14 @@ fn list_of_log_and_compressed_files(
     let o_infix = Some("_r[0-9]*");
 
     let log_pattern = file_spec.as_glob_pattern(o_infix, None);
-    let zip_pattern = file_spec.as_glob_pattern(o_infix, Some("zip"));
     let gz_pattern = file_spec.as_glob_pattern(o_infix, Some("gz"));
+    let zip_pattern = file_spec.as_glob_pattern(o_infix, Some("zip"));
+    let zst_pattern = file_spec.as_glob_pattern(o_infix, Some("zst"));
 
     list_of_files(&log_pattern)
         .chain(list_of_files(&gz_pattern))
         .chain(list_of_files(&zip_pattern))
+        .chain(list_of_files(&zst_pattern))
 }
 
 fn list_of_files(pattern: &str) -> std::vec::IntoIter<PathBuf> {
@@ -491,44 +1115,153 @@ fn remove_or_compress_too_old_logfiles(
     )
 }
 
+/// Codec used by cleanup to compress rotated log files that fall outside the
+/// [`Cleanup::KeepLogFiles`] window.
+///
+/// Carried by [`Cleanup::KeepCompressedFiles`] and [`Cleanup::KeepLogAndCompressedFiles`], so
+/// that users can trade CPU for smaller archives (`Zstd`) instead of being stuck with gzip.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    /// Compress with gzip, producing a `.log.gz` file. Requires feature `compress`.
+    Gzip,
+    /// Compress with zstd, producing a `.log.zst` file. Requires feature `zstd`.
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// Store in a zip archive, producing a `.log.zip` file. Requires feature `zip`.
+    #[cfg(feature = "zip")]
+    Zip,
+}
+impl Compression {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip => "gz",
+            #[cfg(feature = "zstd")]
+            Self::Zstd => "zst",
+            #[cfg(feature = "zip")]
+            Self::Zip => "zip",
+        }
+    }
+
+    fn compress(self, src: &Path) -> Result<PathBuf, std::io::Error> {
+        match self {
+            Self::Gzip => compress_gzip(src),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => compress_zstd(src),
+            #[cfg(feature = "zip")]
+            Self::Zip => compress_zip(src),
+        }
+    }
+}
+
+// Each `compress_*` stages the compressed output at a `file_spec::tmp_sibling` of the final
+// path and renames it into place once the encoder has fully flushed, so a reader (or a crashed
+// process being restarted) never observes a half-compressed archive at the final path.
+fn compress_gzip(src: &Path) -> Result<PathBuf, std::io::Error> {
+    let mut old_file = File::open(src)?;
+    let mut compressed_file = src.to_path_buf();
+    compressed_file.set_extension("log.gz");
+    let tmp = crate::file_spec::tmp_sibling(&compressed_file);
+    let mut encoder =
+        flate2::write::GzEncoder::new(File::create(&tmp)?, flate2::Compression::fast());
+    std::io::copy(&mut old_file, &mut encoder)?;
+    encoder.finish()?;
+    crate::file_spec::rename_or_copy(&tmp, &compressed_file)?;
+    Ok(compressed_file)
+}
+
+#[cfg(feature = "zstd")]
+fn compress_zstd(src: &Path) -> Result<PathBuf, std::io::Error> {
+    let mut old_file = File::open(src)?;
+    let mut compressed_file = src.to_path_buf();
+    compressed_file.set_extension("log.zst");
+    let tmp = crate::file_spec::tmp_sibling(&compressed_file);
+    let mut encoder = zstd::Encoder::new(File::create(&tmp)?, 0)?;
+    std::io::copy(&mut old_file, &mut encoder)?;
+    encoder.finish()?;
+    crate::file_spec::rename_or_copy(&tmp, &compressed_file)?;
+    Ok(compressed_file)
+}
+
+#[cfg(feature = "zip")]
+fn compress_zip(src: &Path) -> Result<PathBuf, std::io::Error> {
+    let mut old_file = File::open(src)?;
+    let mut compressed_file = src.to_path_buf();
+    compressed_file.set_extension("log.zip");
+    let tmp = crate::file_spec::tmp_sibling(&compressed_file);
+    let mut zip = zip::ZipWriter::new(File::create(&tmp)?);
+    let name = src.file_name().unwrap(/*ok*/).to_string_lossy().to_string();
+    zip.start_file(name, zip::write::FileOptions::default())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    std::io::copy(&mut old_file, &mut zip)?;
+    zip.finish()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    crate::file_spec::rename_or_copy(&tmp, &compressed_file)?;
+    Ok(compressed_file)
+}
+
 fn remove_or_compress_too_old_logfiles_impl(
     cleanup_config: &Cleanup,
     file_spec: &FileSpec,
 ) -> Result<(), std::io::Error> {
-    let (log_limit, compress_limit) = match *cleanup_config {
+    let (log_limit, compress_limit, compression) = match *cleanup_config {
         Cleanup::Never => {
             return Ok(());
         }
-        Cleanup::KeepLogFiles(log_limit) => (log_limit, 0),
+        Cleanup::KeepLogFiles(log_limit) => (log_limit, 0, Compression::Gzip),
 
         #[cfg(feature = "compress")]
-        Cleanup::KeepCompressedFiles(compress_limit) => (0, compress_limit),
+        Cleanup::KeepCompressedFiles(compress_limit, compression) => {
+            (0, compress_limit, compression)
+        }
 
         #[cfg(feature = "compress")]
-        Cleanup::KeepLogAndCompressedFiles(log_limit, compress_limit) => {
-            (log_limit, compress_limit)
+        Cleanup::KeepLogAndCompressedFiles(log_limit, compress_limit, compression) => {
+            (log_limit, compress_limit, compression)
+        }
+
+        Cleanup::KeepForDuration(max_age) => {
+            for file in list_of_log_and_compressed_files(&file_spec) {
+                if rotated_file_age(&file)? > max_age {
+                    std::fs::remove_file(&file)?;
+                }
+            }
+            return Ok(());
+        }
+
+        Cleanup::KeepForDurationAndCount(max_age, count) => {
+            for (index, file) in list_of_log_and_compressed_files(&file_spec).enumerate() {
+                if index >= count || rotated_file_age(&file)? > max_age {
+                    std::fs::remove_file(&file)?;
+                }
+            }
+            return Ok(());
+        }
+
+        Cleanup::KeepBytes(max_bytes) => {
+            // list_of_log_and_compressed_files yields newest-first, so once the running total
+            // crosses the budget, this file and everything older gets removed.
+            let mut total_bytes = 0_u64;
+            for file in list_of_log_and_compressed_files(&file_spec) {
+                total_bytes += std::fs::metadata(&file)?.len();
+                if total_bytes > max_bytes {
+                    std::fs::remove_file(&file)?;
+                }
+            }
+            return Ok(());
         }
     };
 
     for (index, file) in list_of_log_and_compressed_files(&file_spec).enumerate() {
         if index >= log_limit + compress_limit {
-            // delete (log or log.gz)
+            // delete (log, or already compressed)
             std::fs::remove_file(&file)?;
         } else if index >= log_limit {
             #[cfg(feature = "compress")]
             {
-                // compress, if not yet compressed
+                // compress, if not yet compressed with the configured codec
                 if let Some(extension) = file.extension() {
-                    if extension != "gz" {
-                        let mut old_file = File::open(file.clone())?;
-                        let mut compressed_file = file.clone();
-                        compressed_file.set_extension("log.gz");
-                        let mut gz_encoder = flate2::write::GzEncoder::new(
-                            File::create(compressed_file)?,
-                            flate2::Compression::fast(),
-                        );
-                        std::io::copy(&mut old_file, &mut gz_encoder)?;
-                        gz_encoder.finish()?;
+                    if extension != compression.extension() {
+                        compression.compress(&file)?;
                         std::fs::remove_file(&file)?;
                     }
                 }
@@ -539,6 +1272,35 @@ fn remove_or_compress_too_old_logfiles_impl(
     Ok(())
 }
 
+// With `FlWriteMode::DirectCompress`, the active file is written with a `.gz` suffix;
+// rotated files must keep carrying that suffix so the renames in the `rotate_output_file_*`
+// functions address the right path.
+#[allow(unused_variables)]
+fn with_direct_compress_suffix(mut p: PathBuf, config: &Config) -> PathBuf {
+    #[cfg(feature = "compress")]
+    if matches!(config.write_mode, super::FlWriteMode::DirectCompress(_)) {
+        let mut name = p.into_os_string();
+        name.push(".gz");
+        p = PathBuf::from(name);
+    }
+    p
+}
+
+// Used by `Naming::Timestamps`: one rotated file per rotation, named after the second.
+const DATED_TIMESTAMP_FORMAT: &str = "_r%Y-%m-%d_%H-%M-%S";
+// Used by `Naming::Daily`: one file per calendar day, named after the date only.
+const DATED_DAY_FORMAT: &str = "_%Y_%m_%d";
+
+// Renders `creation_date` with `format`, in UTC when `config.use_utc` is set. The timestamps
+// inside the file's log lines are rendered by the format functions and do not follow this flag.
+fn format_rotation_timestamp(creation_date: &DateTime<Local>, format: &str, config: &Config) -> String {
+    if config.use_utc {
+        creation_date.with_timezone(&chrono::Utc).format(format).to_string()
+    } else {
+        creation_date.format(format).to_string()
+    }
+}
+
 // Moves the current file to the timestamp of the CURRENT file's creation date.
 // If the rotation comes very fast, the new timestamp would be equal to the old one.
 // To avoid file collisions, we insert an additional string to the filename (".restart-<number>").
@@ -547,12 +1309,17 @@ fn remove_or_compress_too_old_logfiles_impl(
 // second, we need to continue with the restart-incrementing.
 fn rotate_output_file_to_date(
     creation_date: &DateTime<Local>,
+    format: &str,
     config: &Config,
 ) -> Result<(), std::io::Error> {
-    let current_path = config.file_spec.as_pathbuf(Some(CURRENT_INFIX));
-    let mut rotated_path = config.file_spec.as_pathbuf(Some(
-        &creation_date.format("_r%Y-%m-%d_%H-%M-%S").to_string(),
-    ));
+    let formatted_date = format_rotation_timestamp(creation_date, format, config);
+
+    let current_path =
+        with_direct_compress_suffix(config.file_spec.as_pathbuf(Some(CURRENT_INFIX)), config);
+    let mut rotated_path = with_direct_compress_suffix(
+        config.file_spec.as_pathbuf(Some(&formatted_date)),
+        config,
+    );
 
     // Search for rotated_path as is and for restart-siblings;
     // if any exists, find highest restart and add 1, else continue without restart
@@ -581,11 +1348,9 @@ fn rotate_output_file_to_date(
 
         while (*rotated_path).exists() {
             rotated_path = config.file_spec.as_pathbuf(Some(
-                &creation_date
-                    .format("_r%Y-%m-%d_%H-%M-%S")
-                    .to_string()
-                    .add(&format!(".restart-{:04}", number)),
+                &formatted_date.clone().add(&format!(".restart-{:04}", number)),
             ));
+            rotated_path = with_direct_compress_suffix(rotated_path, config);
             number += 1;
         }
     }
@@ -615,8 +1380,11 @@ fn rotate_output_file_to_idx(
     };
 
     match std::fs::rename(
-        config.file_spec.as_pathbuf(Some(CURRENT_INFIX)),
-        config.file_spec.as_pathbuf(Some(&number_infix(new_idx))),
+        with_direct_compress_suffix(config.file_spec.as_pathbuf(Some(CURRENT_INFIX)), config),
+        with_direct_compress_suffix(
+            config.file_spec.as_pathbuf(Some(&number_infix(new_idx))),
+            config,
+        ),
     ) {
         Ok(()) => Ok(IdxState::Idx(new_idx)),
         Err(e) => {
@@ -630,14 +1398,121 @@ fn rotate_output_file_to_idx(
     }
 }
 
+// Determines how old a rotated log file is. Prefers the timestamp embedded in the file name
+// (robust against filesystem mtime drift, e.g. from backup restores or file copies) and falls
+// back to the file's mtime when the name doesn't carry a parseable timestamp, which is the
+// case with plain `Naming::Numbers` files.
+fn rotated_file_age(file: &Path) -> Result<std::time::Duration, std::io::Error> {
+    if let Some(timestamp) = extract_timestamp_infix(file) {
+        if let Ok(age) = (Local::now() - timestamp).to_std() {
+            return Ok(age);
+        }
+    }
+    std::fs::metadata(file)?
+        .modified()?
+        .elapsed()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+}
+
+// Scans the file stem for a `_rYYYY-MM-DD_HH-MM-SS` infix, as produced by `Naming::Timestamps`
+// and `Naming::TimestampsAndNumbers`.
+fn extract_timestamp_infix(file: &Path) -> Option<DateTime<Local>> {
+    let filename = file.file_stem()?.to_string_lossy().into_owned();
+    let mut rest = filename.as_str();
+    while let Some(idx) = rest.find("_r") {
+        let candidate = &rest[idx + 2..];
+        if candidate.len() >= 19 {
+            if let Ok(naive) =
+                chrono::NaiveDateTime::parse_from_str(&candidate[..19], "%Y-%m-%d_%H-%M-%S")
+            {
+                if let Some(local) = Local.from_local_datetime(&naive).single() {
+                    return Some(local);
+                }
+            }
+        }
+        rest = &rest[idx + 2..];
+    }
+    None
+}
+
+// Moves the current file to a name combining the given creation timestamp with the next
+// available index for that timestamp, e.g. `<basename>_r2024-01-01_10-00-00_r00000.log`.
+// Unlike `rotate_output_file_to_date`, collisions within the same second are never resolved
+// by scanning for `.restart-N` siblings; the embedded index is simply incremented instead.
+fn rotate_output_file_to_timestamp_and_idx(
+    creation_date: &DateTime<Local>,
+    config: &Config,
+) -> Result<(), std::io::Error> {
+    let timestamp_infix = format_rotation_timestamp(creation_date, "_r%Y-%m-%d_%H-%M-%S", config);
+    let new_idx = match get_highest_rotate_idx_for_timestamp(&config.file_spec, &timestamp_infix) {
+        IdxState::Start => 0,
+        IdxState::Idx(idx) => idx + 1,
+    };
+
+    match std::fs::rename(
+        with_direct_compress_suffix(config.file_spec.as_pathbuf(Some(CURRENT_INFIX)), config),
+        with_direct_compress_suffix(
+            config.file_spec.as_pathbuf(Some(&format!(
+                "{}{}",
+                timestamp_infix,
+                number_infix(new_idx)
+            ))),
+            config,
+        ),
+    ) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                // current did not exist, so we had nothing to do
+                Ok(())
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
+
+// Scans existing rotated files for the highest index already used with the given timestamp
+// infix, so that repeated rotations within the same second keep incrementing instead of
+// colliding.
+fn get_highest_rotate_idx_for_timestamp(file_spec: &FileSpec, timestamp_infix: &str) -> IdxState {
+    let mut highest_idx = IdxState::Start;
+    for file in list_of_log_and_compressed_files(file_spec) {
+        let filename = file.file_stem().unwrap(/*ok*/).to_string_lossy();
+        if let Some(i) = filename.find(timestamp_infix) {
+            let after = &filename[i + timestamp_infix.len()..];
+            if let Some(idx_str) = after.strip_prefix("_r") {
+                if let Ok(idx) = idx_str.parse::<u32>() {
+                    highest_idx = match highest_idx {
+                        IdxState::Start => IdxState::Idx(idx),
+                        IdxState::Idx(prev) => IdxState::Idx(max(prev, idx)),
+                    };
+                }
+            }
+        }
+    }
+    highest_idx
+}
+
 // See documentation of Criterion::Age.
 #[allow(unused_variables)]
 fn get_creation_date(path: &Path) -> DateTime<Local> {
     // On windows, we know that try_get_creation_date() returns a result, but it is wrong.
-    // On linux, we know that try_get_creation_date() returns an error.
-    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    #[cfg(target_os = "windows")]
     return get_fake_creation_date();
 
+    // On linux, ask the kernel directly for the inode's birth time via `statx`, since not
+    // every std implementation surfaces it through `Metadata::created()`; fall back to
+    // `created()` and finally to the fake date if the filesystem doesn't report one at all.
+    #[cfg(target_os = "linux")]
+    return match try_get_creation_date_linux(path) {
+        Ok(d) => d,
+        Err(_) => match try_get_creation_date(path) {
+            Ok(d) => d,
+            Err(_) => get_fake_creation_date(),
+        },
+    };
+
     // On all others of the many platforms, we give the real creation date a try,
     // and fall back to the fake if it is not available.
     #[cfg(not(any(target_os = "windows", target_os = "linux")))]
@@ -651,32 +1526,83 @@ fn get_fake_creation_date() -> DateTime<Local> {
     Local::now()
 }
 
-#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+#[cfg(not(target_os = "windows"))]
 fn try_get_creation_date(path: &Path) -> Result<DateTime<Local>, FlexiLoggerError> {
     Ok(std::fs::metadata(path)?.created()?.into())
 }
 
+// Reads the real inode birth time via the `statx(2)` syscall with the `STATX_BTIME` mask.
+// Not all filesystems populate `stx_btime` (e.g. older ext4 without the feature), and old
+// kernels don't implement `statx` at all (`ENOSYS`); both cases are reported as an error so
+// the caller can fall back to `Metadata::created()` and, ultimately, to `Local::now()`.
+#[cfg(target_os = "linux")]
+fn try_get_creation_date_linux(path: &Path) -> Result<DateTime<Local>, FlexiLoggerError> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).map_err(|e| {
+        FlexiLoggerError::OutputIo(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+    })?;
+
+    let mut statx_buf: libc::statx = unsafe { std::mem::zeroed() };
+    let ret = unsafe {
+        libc::statx(
+            libc::AT_FDCWD,
+            c_path.as_ptr(),
+            libc::AT_STATX_SYNC_AS_STAT,
+            libc::STATX_BTIME,
+            &mut statx_buf,
+        )
+    };
+    if ret != 0 {
+        // covers both a hard failure and `ENOSYS` on kernels without `statx`
+        return Err(FlexiLoggerError::OutputIo(std::io::Error::last_os_error()));
+    }
+    if statx_buf.stx_mask & libc::STATX_BTIME == 0 {
+        return Err(FlexiLoggerError::OutputIo(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "filesystem does not report a creation time (STATX_BTIME)",
+        )));
+    }
+
+    chrono::NaiveDateTime::from_timestamp_opt(
+        statx_buf.stx_btime.tv_sec,
+        statx_buf.stx_btime.tv_nsec,
+    )
+    .map(|naive| Local.from_utc_datetime(&naive))
+    .ok_or_else(|| {
+        FlexiLoggerError::OutputIo(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "implausible btime returned by statx",
+        ))
+    })
+}
+
 mod platform {
     use std::path::Path;
 
     pub fn create_symlink_if_possible(link: &Path, path: &Path) {
         linux_create_symlink(link, path);
+        windows_create_symlink(link, path);
     }
 
+    // Creates the new symlink at a `file_spec::tmp_sibling` of `link` first, then renames it onto
+    // `link`. `rename` atomically replaces an existing symlink on POSIX, so there is never a
+    // window where `link` exists but points nowhere, unlike a `remove_file` followed by a
+    // separate `symlink` call.
     #[cfg(target_os = "linux")]
     fn linux_create_symlink(link: &Path, logfile: &Path) {
-        if std::fs::symlink_metadata(link).is_ok() {
-            // remove old symlink before creating a new one
-            if let Err(e) = std::fs::remove_file(link) {
-                eprintln!(
-                    "[flexi_logger] deleting old symlink to log file failed with {:?}",
-                    e
-                );
-            }
+        let tmp = crate::file_spec::tmp_sibling(link);
+        if let Err(e) = std::os::unix::fs::symlink(&logfile, &tmp) {
+            eprintln!(
+                "[flexi_logger] cannot create symlink {:?} for logfile \"{}\" due to {:?}",
+                link,
+                &logfile.display(),
+                e
+            );
+            return;
         }
 
-        // create new symlink
-        if let Err(e) = std::os::unix::fs::symlink(&logfile, link) {
+        if let Err(e) = std::fs::rename(&tmp, link) {
             eprintln!(
                 "[flexi_logger] cannot create symlink {:?} for logfile \"{}\" due to {:?}",
                 link,
@@ -688,4 +1614,43 @@ mod platform {
 
     #[cfg(not(target_os = "linux"))]
     fn linux_create_symlink(_: &Path, _: &Path) {}
+
+    // Mirrors `linux_create_symlink`, branching on whether the target is a file or a
+    // directory, since Windows' `symlink_file`/`symlink_dir` and `remove_file`/`remove_dir`
+    // are not interchangeable the way Unix's single `symlink`/`remove_file` are.
+    #[cfg(target_os = "windows")]
+    fn windows_create_symlink(link: &Path, logfile: &Path) {
+        let target_is_dir = logfile.is_dir();
+        let tmp = crate::file_spec::tmp_sibling(link);
+
+        // Creating symlinks on Windows requires Developer Mode or the
+        // `SeCreateSymbolicLink` privilege; degrade gracefully rather than panic.
+        let created = if target_is_dir {
+            std::os::windows::fs::symlink_dir(logfile, &tmp)
+        } else {
+            std::os::windows::fs::symlink_file(logfile, &tmp)
+        };
+        if let Err(e) = created {
+            eprintln!(
+                "[flexi_logger] cannot create symlink {:?} for logfile \"{}\" due to {:?}",
+                link,
+                &logfile.display(),
+                e
+            );
+            return;
+        }
+
+        // `rename` replaces an existing file/dir symlink at `link` atomically, same as on Linux.
+        if let Err(e) = std::fs::rename(&tmp, link) {
+            eprintln!(
+                "[flexi_logger] cannot create symlink {:?} for logfile \"{}\" due to {:?}",
+                link,
+                &logfile.display(),
+                e
+            );
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn windows_create_symlink(_: &Path, _: &Path) {}
 }
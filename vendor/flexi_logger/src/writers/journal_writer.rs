@@ -0,0 +1,357 @@
+use crate::deferred_now::DeferredNow;
+use crate::writers::LogWriter;
+use crate::{FlexiLoggerError, FormatFunction};
+use log::Record;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixDatagram;
+use std::sync::Mutex;
+
+const JOURNAL_SOCKET_PATH: &str = "/run/systemd/journal/socket";
+
+// Entries are buffered up to this count and sent as a batch of individual `sendto()` calls
+// (the journal native protocol has no true multi-entry datagram) so that bursty logging
+// doesn't pay one syscall per record; `flush()` always drains whatever is pending regardless
+// of how many have accumulated.
+const BATCH_SIZE: usize = 32;
+
+// Above this size, a plain `sendto()` of the encoded entry is no longer reliable (the kernel's
+// unix-datagram buffer and journald's own receive buffer both have practical ceilings well
+// below `u64::MAX`), so we fall back to the memfd/SCM_RIGHTS path instead.
+const DATAGRAM_SIZE_THRESHOLD: usize = 200 * 1024;
+
+// Derives the `SYSLOG_IDENTIFIER` journal field from the running executable's file name, the
+// same convention the standard `sd_journal_print`/syslog APIs use when no identifier is set
+// explicitly.
+fn current_program_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "flexi_logger".to_string())
+}
+
+// Maps a `log::Level` to the syslog-style priority the journal expects.
+fn level_to_priority(level: log::Level) -> u8 {
+    match level {
+        log::Level::Error => 3,
+        log::Level::Warn => 4,
+        log::Level::Info => 6,
+        log::Level::Debug | log::Level::Trace => 7,
+    }
+}
+
+// Journal field names must be uppercase ASCII letters, digits, and underscores, and must not
+// start with a digit or an underscore; anything else gets coerced into that shape.
+fn sanitize_field_name(key: &str) -> String {
+    let mut name = String::with_capacity(key.len());
+    for c in key.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_uppercase());
+        } else {
+            name.push('_');
+        }
+    }
+    while name.starts_with('_') {
+        name.remove(0);
+    }
+    if name.is_empty() || name.as_bytes()[0].is_ascii_digit() {
+        name.insert_str(0, "KV_");
+    }
+    name
+}
+
+struct KvFieldsVisitor<'a>(&'a mut Vec<u8>);
+impl<'a, 'kvs> log::kv::Visitor<'kvs> for KvFieldsVisitor<'a> {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        JournalLogWriter::push_field(self.0, &sanitize_field_name(key.as_str()), &value.to_string());
+        Ok(())
+    }
+}
+
+/// Writes log records as structured fields (message, syslog identifier, module path, line,
+/// priority, and any `log::kv` pairs) to the local `systemd-journald` socket, using journald's
+/// native datagram protocol.
+///
+/// Registered via [`Logger::log_to_journal`](crate::Logger::log_to_journal), gated behind the
+/// `journal` feature and only available on Linux.
+pub struct JournalLogWriter {
+    socket: UnixDatagram,
+    max_log_level: log::LevelFilter,
+    format: Mutex<FormatFunction>,
+    pending: Mutex<VecDeque<Vec<u8>>>,
+    syslog_identifier: String,
+}
+impl JournalLogWriter {
+    /// Connects to the local systemd journal socket.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::JournalSocket`] if the journal socket cannot be reached.
+    pub fn try_new(max_log_level: log::LevelFilter) -> Result<Self, FlexiLoggerError> {
+        let socket = UnixDatagram::unbound().map_err(FlexiLoggerError::JournalSocket)?;
+        socket
+            .connect(JOURNAL_SOCKET_PATH)
+            .map_err(FlexiLoggerError::JournalSocket)?;
+        Ok(Self {
+            socket,
+            max_log_level,
+            format: Mutex::new(crate::formats::default_format),
+            pending: Mutex::new(VecDeque::with_capacity(BATCH_SIZE)),
+            syslog_identifier: current_program_name(),
+        })
+    }
+
+    // Sends one already-encoded datagram, routing oversized entries through the memfd path.
+    fn send_one(&self, datagram: &[u8]) -> std::io::Result<()> {
+        if datagram.len() > DATAGRAM_SIZE_THRESHOLD {
+            self.send_via_memfd(datagram)
+        } else {
+            self.socket.send(datagram).map(|_| ())
+        }
+    }
+
+    // Encodes one journal field using the native protocol: `NAME=value\n` for simple
+    // values, or `NAME\n<len as little-endian u64><raw bytes>\n` if the value contains
+    // a newline.
+    fn push_field(buf: &mut Vec<u8>, name: &str, value: &str) {
+        if value.contains('\n') {
+            buf.extend_from_slice(name.as_bytes());
+            buf.push(b'\n');
+            buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            buf.extend_from_slice(value.as_bytes());
+            buf.push(b'\n');
+        } else {
+            buf.extend_from_slice(name.as_bytes());
+            buf.push(b'=');
+            buf.extend_from_slice(value.as_bytes());
+            buf.push(b'\n');
+        }
+    }
+
+    // Sends an entry that's too large for a plain datagram by writing it into a sealed
+    // `memfd` and passing that descriptor to journald as `SCM_RIGHTS` ancillary data on an
+    // otherwise-empty datagram, per the journal native protocol's documented large-message
+    // convention.
+    #[cfg(target_os = "linux")]
+    fn send_via_memfd(&self, datagram: &[u8]) -> std::io::Result<()> {
+        let fd = journal_memfd::create_sealed_memfd(datagram)?;
+        let result = journal_memfd::sendmsg_with_fd(self.socket.as_raw_fd(), fd);
+        unsafe {
+            libc_sys::close(fd);
+        }
+        result
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn send_via_memfd(&self, datagram: &[u8]) -> std::io::Result<()> {
+        // No memfd/SCM_RIGHTS support outside Linux; best effort, same as a plain send.
+        self.socket.send(datagram).map(|_| ())
+    }
+
+    // Sends entries from the front of `pending`, one at a time, only removing each one once
+    // it's actually been sent. If `send_one` fails partway through, the failed entry and
+    // everything behind it stay queued for the next `write`/`flush` call to retry, instead of
+    // being silently discarded the way an unconditional `drain(..)` would.
+    fn send_pending(&self, pending: &mut VecDeque<Vec<u8>>) -> std::io::Result<()> {
+        while let Some(entry) = pending.front() {
+            self.send_one(entry)?;
+            pending.pop_front();
+        }
+        Ok(())
+    }
+}
+impl LogWriter for JournalLogWriter {
+    fn format(&mut self, format: FormatFunction) {
+        *self.format.lock().unwrap(/* ok */) = format;
+    }
+
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        let mut message = Vec::<u8>::with_capacity(200);
+        let format = *self.format.lock().unwrap(/* ok */);
+        format(&mut message, now, record)?;
+
+        let mut datagram = Vec::<u8>::with_capacity(256);
+        Self::push_field(
+            &mut datagram,
+            "PRIORITY",
+            &level_to_priority(record.level()).to_string(),
+        );
+        Self::push_field(
+            &mut datagram,
+            "MESSAGE",
+            &String::from_utf8_lossy(&message),
+        );
+        Self::push_field(&mut datagram, "SYSLOG_IDENTIFIER", &self.syslog_identifier);
+        Self::push_field(&mut datagram, "TARGET", record.target());
+        if let Some(module_path) = record.module_path() {
+            Self::push_field(&mut datagram, "CODE_MODULE", module_path);
+        }
+        if let Some(file) = record.file() {
+            Self::push_field(&mut datagram, "CODE_FILE", file);
+        }
+        if let Some(line) = record.line() {
+            Self::push_field(&mut datagram, "CODE_LINE", &line.to_string());
+        }
+        record
+            .key_values()
+            .visit(&mut KvFieldsVisitor(&mut datagram))
+            .ok();
+
+        let mut pending = self.pending.lock().unwrap(/* ok */);
+        pending.push_back(datagram);
+        if pending.len() >= BATCH_SIZE {
+            self.send_pending(&mut pending)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        let mut pending = self.pending.lock().unwrap(/* ok */);
+        self.send_pending(&mut pending)
+    }
+
+    fn max_log_level(&self) -> log::LevelFilter {
+        self.max_log_level
+    }
+
+    fn shutdown(&self) {
+        self.flush().ok();
+    }
+}
+
+// Raw syscall plumbing for the oversized-entry fallback: create a sealed, anonymous,
+// in-memory file holding the full entry and hand its descriptor to journald via
+// `SCM_RIGHTS`. Kept separate from the rest of the writer since none of it is needed on the
+// (much more common) small-message path, and it would otherwise dwarf the writer logic above.
+#[cfg(target_os = "linux")]
+mod journal_memfd {
+    use std::io::Write;
+    use std::os::unix::io::RawFd;
+
+    const MFD_ALLOW_SEALING: super::libc_sys::c_uint = 0x0002;
+    const F_ADD_SEALS: super::libc_sys::c_int = 1033;
+    const F_SEAL_SHRINK: super::libc_sys::c_int = 0x0002;
+    const F_SEAL_GROW: super::libc_sys::c_int = 0x0004;
+    const F_SEAL_WRITE: super::libc_sys::c_int = 0x0008;
+    const F_SEAL_SEAL: super::libc_sys::c_int = 0x0001;
+
+    pub(super) fn create_sealed_memfd(contents: &[u8]) -> std::io::Result<RawFd> {
+        let name = b"flexi_logger-journal-entry\0";
+        let fd = unsafe { super::libc_sys::memfd_create(name.as_ptr().cast(), MFD_ALLOW_SEALING) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // Safety: `fd` was just created above and is owned exclusively by this function until
+        // it is handed off (via `sendmsg_with_fd`) or closed by the caller on error.
+        let mut file = unsafe { <std::fs::File as std::os::unix::io::FromRawFd>::from_raw_fd(fd) };
+        if let Err(e) = file.write_all(contents) {
+            std::mem::forget(file);
+            unsafe {
+                super::libc_sys::close(fd);
+            }
+            return Err(e);
+        }
+        std::mem::forget(file); // ownership is transferred to the raw fd we return
+
+        let seals = F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE | F_SEAL_SEAL;
+        if unsafe { super::libc_sys::fcntl(fd, F_ADD_SEALS, seals) } < 0 {
+            // Sealing is an optimization hint for journald, not a correctness requirement;
+            // keep going with the unsealed memfd if the kernel refuses it.
+        }
+        Ok(fd)
+    }
+
+    pub(super) fn sendmsg_with_fd(socket: RawFd, fd: RawFd) -> std::io::Result<()> {
+        #[repr(C)]
+        struct Msghdr {
+            msg_name: *mut super::libc_sys::c_void,
+            msg_namelen: super::libc_sys::socklen_t,
+            msg_iov: *mut Iovec,
+            msg_iovlen: super::libc_sys::size_t,
+            msg_control: *mut super::libc_sys::c_void,
+            msg_controllen: super::libc_sys::size_t,
+            msg_flags: super::libc_sys::c_int,
+        }
+        #[repr(C)]
+        struct Iovec {
+            iov_base: *mut super::libc_sys::c_void,
+            iov_len: super::libc_sys::size_t,
+        }
+        #[repr(C)]
+        struct Cmsghdr {
+            cmsg_len: super::libc_sys::size_t,
+            cmsg_level: super::libc_sys::c_int,
+            cmsg_type: super::libc_sys::c_int,
+        }
+        const SOL_SOCKET: super::libc_sys::c_int = 1;
+        const SCM_RIGHTS: super::libc_sys::c_int = 1;
+
+        // One byte of real payload: journald ignores the data and reads the entry from the
+        // fd, but an entirely empty datagram is easy to mistake for a closed connection.
+        let mut iov_buf = [0u8; 1];
+        let mut iov = Iovec {
+            iov_base: iov_buf.as_mut_ptr().cast(),
+            iov_len: iov_buf.len(),
+        };
+
+        let cmsg_space = std::mem::size_of::<Cmsghdr>() + std::mem::size_of::<RawFd>();
+        let mut control = vec![0u8; cmsg_space];
+        {
+            let header = control.as_mut_ptr().cast::<Cmsghdr>();
+            unsafe {
+                (*header).cmsg_len = cmsg_space;
+                (*header).cmsg_level = SOL_SOCKET;
+                (*header).cmsg_type = SCM_RIGHTS;
+                let fd_ptr = control
+                    .as_mut_ptr()
+                    .add(std::mem::size_of::<Cmsghdr>())
+                    .cast::<RawFd>();
+                fd_ptr.write_unaligned(fd);
+            }
+        }
+
+        let mut msg = Msghdr {
+            msg_name: std::ptr::null_mut(),
+            msg_namelen: 0,
+            msg_iov: &mut iov,
+            msg_iovlen: 1,
+            msg_control: control.as_mut_ptr().cast(),
+            msg_controllen: cmsg_space,
+            msg_flags: 0,
+        };
+
+        let sent = unsafe {
+            super::libc_sys::sendmsg(socket, (&mut msg as *mut Msghdr).cast(), 0)
+        };
+        if sent < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Minimal raw FFI surface for the handful of Linux syscalls the oversized-entry fallback
+// needs. Declared by hand, matching glibc's stable Linux x86_64/aarch64 ABI, so this file
+// doesn't have to pull in an external `libc` dependency just for `memfd_create`/`sendmsg`.
+#[cfg(target_os = "linux")]
+mod libc_sys {
+    #![allow(non_camel_case_types)]
+    pub type c_void = std::ffi::c_void;
+    pub type c_int = i32;
+    pub type c_uint = u32;
+    pub type size_t = usize;
+    pub type socklen_t = u32;
+
+    extern "C" {
+        pub fn memfd_create(name: *const i8, flags: c_uint) -> c_int;
+        pub fn fcntl(fd: c_int, cmd: c_int, arg: c_int) -> c_int;
+        pub fn close(fd: c_int) -> c_int;
+        pub fn sendmsg(sockfd: c_int, msg: *mut c_void, flags: c_int) -> isize;
+    }
+}
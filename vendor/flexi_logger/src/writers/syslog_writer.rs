@@ -0,0 +1,391 @@
+use crate::deferred_now::DeferredNow;
+use crate::writers::LogWriter;
+use crate::{FlexiLoggerError, FormatFunction};
+use log::Record;
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::os::unix::net::{UnixDatagram, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Maps a `log::Level` to the syslog severity it corresponds to (the facility is supplied
+// separately by the builder). PRI = facility * 8 + severity.
+fn level_to_severity(level: log::Level) -> u8 {
+    match level {
+        log::Level::Error => 3,
+        log::Level::Warn => 4,
+        log::Level::Info => 6,
+        log::Level::Debug | log::Level::Trace => 7,
+    }
+}
+
+/// Where to send syslog datagrams/lines to.
+pub enum SyslogTransport {
+    /// Connects to a Unix datagram socket, e.g. `/dev/log`.
+    UnixDatagram(PathBuf),
+    /// Connects to a Unix stream socket.
+    UnixStream(PathBuf),
+    /// Sends each message as a UDP datagram to the given address.
+    Udp(SocketAddr),
+    /// Sends each message, octet-counted per RFC 6587, over a long-lived TCP connection to
+    /// the given address. Reconnects with exponential backoff on failure; records are queued
+    /// in memory (bounded) rather than blocking the calling thread while disconnected.
+    Tcp(SocketAddr),
+    /// Like [`SyslogTransport::Tcp`], but the connection is wrapped in TLS. The `server_name`
+    /// is used for certificate verification (SNI).
+    #[cfg(feature = "syslog_tls")]
+    TcpTls(SocketAddr, String),
+}
+
+// Exponential backoff for TCP (re)connect attempts: start small so a blip recovers fast,
+// cap so a prolonged outage doesn't leave the background thread sleeping for unreasonably
+// long between retries.
+const TCP_RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const TCP_RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+// Bounds how many not-yet-sent, already-framed messages are held in memory while the TCP
+// connection is down or being (re)established. Once full, `write()` applies backpressure
+// (blocks) rather than growing without bound or silently dropping records; a transient outage
+// shorter than this capacity's drain time is fully absorbed without blocking callers at all.
+const TCP_QUEUE_CAPACITY: usize = 1024;
+
+// A TCP (optionally TLS) stream, reconnected with backoff by a dedicated background thread so
+// that `SyslogWriter::write` never blocks on the network itself -- it only ever blocks on the
+// bounded in-memory queue, and only once that queue is full.
+struct TcpSender {
+    queue: SyncSender<Vec<u8>>,
+    join_handle: Option<std::thread::JoinHandle<()>>,
+}
+impl TcpSender {
+    fn spawn(target: TcpStreamTarget) -> Self {
+        let (queue, receiver) = sync_channel::<Vec<u8>>(TCP_QUEUE_CAPACITY);
+        let join_handle = std::thread::Builder::new()
+            .name("flexi_logger-syslog-tcp".to_string())
+            .spawn(move || {
+                let mut stream: Option<Box<dyn Write + Send>> = None;
+                let mut backoff = TCP_RECONNECT_INITIAL_BACKOFF;
+                while let Ok(framed) = receiver.recv() {
+                    loop {
+                        if stream.is_none() {
+                            match target.connect() {
+                                Ok(s) => {
+                                    stream = Some(s);
+                                    backoff = TCP_RECONNECT_INITIAL_BACKOFF;
+                                }
+                                Err(_) => {
+                                    std::thread::sleep(backoff);
+                                    backoff = std::cmp::min(backoff * 2, TCP_RECONNECT_MAX_BACKOFF);
+                                    continue;
+                                }
+                            }
+                        }
+                        if let Some(s) = stream.as_mut() {
+                            if s.write_all(&framed).is_ok() {
+                                break;
+                            }
+                            // Write failed: drop the stale connection and retry the same
+                            // message once a new one has been (re)established.
+                            stream = None;
+                        }
+                    }
+                }
+            })
+            .expect("failed to spawn flexi_logger-syslog-tcp thread");
+        Self {
+            queue,
+            join_handle: Some(join_handle),
+        }
+    }
+
+    fn send(&self, framed: Vec<u8>) -> std::io::Result<()> {
+        self.queue
+            .send(framed)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "syslog TCP sender thread has exited"))
+    }
+}
+impl Drop for TcpSender {
+    fn drop(&mut self) {
+        // Dropping `queue` (implicit, as a field drop) disconnects the channel, which ends
+        // the background thread's `receiver.recv()` loop; join it so in-flight output has a
+        // chance to drain before the writer itself goes away.
+        if let Some(handle) = self.join_handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+// How the background TCP thread (re)establishes its connection; kept separate from
+// `SyslogTransport` since it's only relevant once inside that thread, not at the public API.
+enum TcpStreamTarget {
+    Plain(SocketAddr),
+    #[cfg(feature = "syslog_tls")]
+    Tls(SocketAddr, String),
+}
+impl TcpStreamTarget {
+    fn connect(&self) -> std::io::Result<Box<dyn Write + Send>> {
+        match self {
+            Self::Plain(addr) => Ok(Box::new(TcpStream::connect(addr)?)),
+            #[cfg(feature = "syslog_tls")]
+            Self::Tls(addr, server_name) => {
+                let tcp = TcpStream::connect(addr)?;
+                let mut roots = rustls::RootCertStore::empty();
+                roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+                let config = std::sync::Arc::new(
+                    rustls::ClientConfig::builder()
+                        .with_safe_defaults()
+                        .with_root_certificates(roots)
+                        .with_no_client_auth(),
+                );
+                let name = rustls::ServerName::try_from(server_name.as_str())
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+                let conn = rustls::ClientConnection::new(config, name)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                Ok(Box::new(rustls::StreamOwned::new(conn, tcp)))
+            }
+        }
+    }
+}
+
+// Frames a message for TCP transport using RFC 6587 octet-counting: the decimal length of
+// `message` in bytes, a single space, then the raw message (no trailing delimiter needed,
+// since the receiver already knows exactly how many bytes to read from the length prefix).
+fn frame_octet_counted(message: &[u8]) -> Vec<u8> {
+    let mut framed = format!("{} ", message.len()).into_bytes();
+    framed.extend_from_slice(message);
+    framed
+}
+
+/// Which syslog message framing to use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SyslogFormat {
+    /// The legacy BSD framing from RFC 3164: `<PRI>TIMESTAMP HOSTNAME TAG: MSG`.
+    Rfc3164,
+    /// The modern framing from RFC 5424, including structured data derived from the
+    /// record's key-value pairs.
+    Rfc5424,
+}
+
+enum SyslogHandle {
+    UnixDatagram(UnixDatagram),
+    UnixStream(Mutex<UnixStream>),
+    Udp(UdpSocket),
+    Tcp(TcpSender),
+}
+impl SyslogHandle {
+    fn send(&self, message: &[u8]) -> std::io::Result<()> {
+        match self {
+            Self::UnixDatagram(socket) => socket.send(message).map(|_| ()),
+            Self::UnixStream(stream) => stream.lock().unwrap(/* ok */).write_all(message),
+            Self::Udp(socket) => socket.send(message).map(|_| ()),
+            Self::Tcp(sender) => sender.send(frame_octet_counted(message)),
+        }
+    }
+}
+
+/// Builder for [`SyslogWriter`].
+pub struct SyslogWriterBuilder {
+    transport: SyslogTransport,
+    format: SyslogFormat,
+    facility: u8,
+    app_name: String,
+    proc_id: String,
+    max_log_level: log::LevelFilter,
+}
+impl SyslogWriterBuilder {
+    /// Creates a builder that sends to the given transport, using RFC 5424 framing, the
+    /// `user` facility (1), and the current program name and process id.
+    #[must_use]
+    pub fn new(transport: SyslogTransport) -> Self {
+        Self {
+            transport,
+            format: SyslogFormat::Rfc5424,
+            facility: 1, // user-level messages
+            app_name: std::env::args()
+                .next()
+                .and_then(|s| Path::new(&s).file_name().map(|f| f.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "-".to_string()),
+            proc_id: std::process::id().to_string(),
+            max_log_level: log::LevelFilter::Trace,
+        }
+    }
+
+    /// Selects BSD (RFC 3164) or RFC 5424 message framing. Default is RFC 5424.
+    #[must_use]
+    pub fn format(mut self, format: SyslogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the syslog facility (0..=23). Default is 1 (`user`).
+    #[must_use]
+    pub fn facility(mut self, facility: u8) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    /// Overrides the APP-NAME field. Defaults to the current program's file name.
+    #[must_use]
+    pub fn app_name<S: Into<String>>(mut self, app_name: S) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    /// Overrides the PROCID field. Defaults to the current process id.
+    #[must_use]
+    pub fn proc_id<S: Into<String>>(mut self, proc_id: S) -> Self {
+        self.proc_id = proc_id.into();
+        self
+    }
+
+    /// Connects to the configured transport and produces the [`SyslogWriter`].
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::OutputIo`] if the connection to the syslog destination fails.
+    pub fn try_build(self) -> Result<SyslogWriter, FlexiLoggerError> {
+        let handle = match &self.transport {
+            SyslogTransport::UnixDatagram(path) => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                SyslogHandle::UnixDatagram(socket)
+            }
+            SyslogTransport::UnixStream(path) => {
+                SyslogHandle::UnixStream(Mutex::new(UnixStream::connect(path)?))
+            }
+            SyslogTransport::Udp(addr) => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.connect(addr)?;
+                SyslogHandle::Udp(socket)
+            }
+            SyslogTransport::Tcp(addr) => {
+                SyslogHandle::Tcp(TcpSender::spawn(TcpStreamTarget::Plain(*addr)))
+            }
+            #[cfg(feature = "syslog_tls")]
+            SyslogTransport::TcpTls(addr, server_name) => SyslogHandle::Tcp(TcpSender::spawn(
+                TcpStreamTarget::Tls(*addr, server_name.clone()),
+            )),
+        };
+        Ok(SyslogWriter {
+            handle,
+            format: self.format,
+            facility: self.facility,
+            app_name: self.app_name,
+            proc_id: self.proc_id,
+            max_log_level: self.max_log_level,
+            format_function: Mutex::new(crate::formats::default_format),
+        })
+    }
+}
+
+/// Sends log records to a syslog daemon (local or remote), as BSD (RFC 3164) or RFC 5424
+/// framed messages.
+///
+/// Registered via [`Logger::log_to_syslog`](crate::Logger::log_to_syslog).
+pub struct SyslogWriter {
+    handle: SyslogHandle,
+    format: SyslogFormat,
+    facility: u8,
+    app_name: String,
+    proc_id: String,
+    max_log_level: log::LevelFilter,
+    format_function: Mutex<FormatFunction>,
+}
+impl SyslogWriter {
+    /// Shorthand for connecting to the well-known `/dev/log` Unix datagram socket with RFC
+    /// 5424 framing.
+    ///
+    /// # Errors
+    ///
+    /// [`FlexiLoggerError::OutputIo`] if `/dev/log` cannot be reached.
+    pub fn try_default() -> Result<Self, FlexiLoggerError> {
+        SyslogWriterBuilder::new(SyslogTransport::UnixDatagram(PathBuf::from("/dev/log")))
+            .try_build()
+    }
+
+    fn structured_data(record: &Record) -> String {
+        struct KvVisitor(Vec<(String, String)>);
+        impl<'kvs> log::kv::Visitor<'kvs> for KvVisitor {
+            fn visit_pair(
+                &mut self,
+                key: log::kv::Key<'kvs>,
+                value: log::kv::Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                self.0.push((key.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+        let mut visitor = KvVisitor(Vec::new());
+        record.key_values().visit(&mut visitor).ok();
+
+        // `32473` is the IANA "Example" private enterprise number; real deployments should
+        // set their own SD-ID. `target`/`module` are always included so a remote collector
+        // can tell which part of the program a message came from even without the `log::kv`
+        // pairs that are specific to this call site.
+        let mut sd = format!(
+            "[flexi@32473 target=\"{}\"",
+            record.target().replace('"', "\\\"")
+        );
+        if let Some(module_path) = record.module_path() {
+            sd.push_str(&format!(" module=\"{}\"", module_path.replace('"', "\\\"")));
+        }
+        for (key, value) in visitor.0 {
+            sd.push_str(&format!(" {}=\"{}\"", key, value.replace('"', "\\\"")));
+        }
+        sd.push(']');
+        sd
+    }
+
+    fn render(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<Vec<u8>> {
+        let pri = u32::from(self.facility) * 8 + u32::from(level_to_severity(record.level()));
+
+        let mut message = Vec::<u8>::with_capacity(200);
+        let format = *self.format_function.lock().unwrap(/* ok */);
+        format(&mut message, now, record)?;
+        let message = String::from_utf8_lossy(&message);
+
+        Ok(match self.format {
+            SyslogFormat::Rfc3164 => format!(
+                "<{}>{} {} {}: {}",
+                pri,
+                now.now().format("%b %e %T"),
+                std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+                self.app_name,
+                message
+            )
+            .into_bytes(),
+            SyslogFormat::Rfc5424 => format!(
+                "<{}>1 {} {} {} {} - {} {}",
+                pri,
+                now.now().to_rfc3339_opts(chrono::SecondsFormat::Micros, false),
+                std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+                self.app_name,
+                self.proc_id,
+                Self::structured_data(record),
+                message
+            )
+            .into_bytes(),
+        })
+    }
+}
+impl LogWriter for SyslogWriter {
+    fn format(&mut self, format: FormatFunction) {
+        *self.format_function.lock().unwrap(/* ok */) = format;
+    }
+
+    fn write(&self, now: &mut DeferredNow, record: &Record) -> std::io::Result<()> {
+        let buf = self.render(now, record)?;
+        self.handle.send(&buf)
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn max_log_level(&self) -> log::LevelFilter {
+        self.max_log_level
+    }
+
+    fn shutdown(&self) {}
+}
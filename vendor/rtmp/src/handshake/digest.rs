@@ -0,0 +1,252 @@
+// The Adobe "complex"/digest handshake (as opposed to the plain handshake, where C1/S1 carry a
+// zero version field and are just echoed back unvalidated). See the RTMP spec's undocumented
+// digest scheme: a 32-byte HMAC-SHA256 digest is embedded at a data-dependent offset inside one
+// of two 764-byte blocks of the 1536-byte C1/S1 buffer.
+use {
+    super::define::{
+        SchemaVersion, ServerHandshakeState, RTMP_CLIENT_KEY_FIRST_HALF, RTMP_DIGEST_LENGTH,
+        RTMP_HANDSHAKE_SIZE, RTMP_SERVER_KEY, RTMP_SERVER_KEY_FIRST_HALF, RTMP_SERVER_VERSION,
+    },
+    failure::Fail,
+    hmac::{Hmac, Mac},
+    rand::RngCore,
+    sha2::Sha256,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DIGEST_BLOCK_LENGTH: usize = 764;
+// the two possible start offsets of the digest-carrying block within the 1536-byte buffer,
+// indexed by schema: Schema0 keeps its digest in the first block, Schema1 in the second.
+const SCHEMA0_DIGEST_BLOCK_START: usize = 8;
+const SCHEMA1_DIGEST_BLOCK_START: usize = 8 + DIGEST_BLOCK_LENGTH;
+
+#[derive(Debug, Fail)]
+pub enum HandshakeError {
+    #[fail(display = "C1/C2/S1/S2 buffers must be {} bytes, got {}", RTMP_HANDSHAKE_SIZE, _0)]
+    BadLength(usize),
+    #[fail(display = "C1 digest did not validate against either schema")]
+    DigestMismatch,
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; RTMP_DIGEST_LENGTH] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts a key of any length");
+    mac.update(message);
+    let mut digest = [0u8; RTMP_DIGEST_LENGTH];
+    digest.copy_from_slice(&mac.finalize().into_bytes());
+    digest
+}
+
+// The digest offset within `buf`'s 1536 bytes for the block starting at `block_start`: the sum
+// of the 4 "offset" bytes in the block's first 4 bytes, modulo 728 (== 764 - 4 - 32, so the
+// digest always lands fully inside the block), plus the 4 bytes those offset bytes occupy.
+//
+// The block's *last* 4 bytes are a different, unrelated offset used by the DH key block, not
+// this digest block; reading them here rejects every real Flash/ffmpeg/OBS client.
+fn digest_offset(buf: &[u8; RTMP_HANDSHAKE_SIZE], block_start: usize) -> usize {
+    let offset_bytes = &buf[block_start..block_start + 4];
+    let sum: u32 = offset_bytes.iter().map(|&b| u32::from(b)).sum();
+    (sum % 728) as usize + block_start + 4
+}
+
+// The 1536 bytes with the 32 digest bytes at `digest_offset` removed, i.e. the bytes before and
+// after the digest concatenated -- this is what actually gets HMAC'd, not the raw buffer.
+fn bytes_excluding_digest(buf: &[u8; RTMP_HANDSHAKE_SIZE], digest_offset: usize) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RTMP_HANDSHAKE_SIZE - RTMP_DIGEST_LENGTH);
+    message.extend_from_slice(&buf[..digest_offset]);
+    message.extend_from_slice(&buf[digest_offset + RTMP_DIGEST_LENGTH..]);
+    message
+}
+
+fn digest_block_start(schema: SchemaVersion) -> Option<usize> {
+    match schema {
+        SchemaVersion::Schema0 => Some(SCHEMA0_DIGEST_BLOCK_START),
+        SchemaVersion::Schema1 => Some(SCHEMA1_DIGEST_BLOCK_START),
+        SchemaVersion::Unknown => None,
+    }
+}
+
+// Recomputes the digest for `schema` and compares it against the one embedded in `buf`; returns
+// the embedded (== validated) client digest bytes on a match.
+fn validate_schema(
+    buf: &[u8; RTMP_HANDSHAKE_SIZE],
+    schema: SchemaVersion,
+) -> Option<[u8; RTMP_DIGEST_LENGTH]> {
+    let block_start = digest_block_start(schema)?;
+    let offset = digest_offset(buf, block_start);
+    let embedded = &buf[offset..offset + RTMP_DIGEST_LENGTH];
+    let message = bytes_excluding_digest(buf, offset);
+    let computed = hmac_sha256(RTMP_CLIENT_KEY_FIRST_HALF.as_bytes(), &message);
+    if computed == embedded {
+        Some(computed)
+    } else {
+        None
+    }
+}
+
+// Tries both schemas against an incoming C1 and returns whichever one validates, along with the
+// client's digest bytes (needed to build S2).
+fn validate_c1(buf: &[u8; RTMP_HANDSHAKE_SIZE]) -> Option<(SchemaVersion, [u8; RTMP_DIGEST_LENGTH])> {
+    for schema in [SchemaVersion::Schema0, SchemaVersion::Schema1] {
+        if let Some(digest) = validate_schema(buf, schema) {
+            return Some((schema, digest));
+        }
+    }
+    None
+}
+
+fn build_s1(schema: SchemaVersion) -> [u8; RTMP_HANDSHAKE_SIZE] {
+    let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+    buf[4..8].copy_from_slice(&RTMP_SERVER_VERSION);
+    rand::thread_rng().fill_bytes(&mut buf[8..]);
+
+    let block_start = digest_block_start(schema).unwrap_or(SCHEMA1_DIGEST_BLOCK_START);
+    let offset = digest_offset(&buf, block_start);
+    let message = bytes_excluding_digest(&buf, offset);
+    let digest = hmac_sha256(RTMP_SERVER_KEY_FIRST_HALF.as_bytes(), &message);
+    buf[offset..offset + RTMP_DIGEST_LENGTH].copy_from_slice(&digest);
+    buf
+}
+
+fn build_s2(client_digest: &[u8; RTMP_DIGEST_LENGTH]) -> [u8; RTMP_HANDSHAKE_SIZE] {
+    let key = hmac_sha256(&RTMP_SERVER_KEY, client_digest);
+
+    let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+    let (random_part, signature_part) = buf.split_at_mut(RTMP_HANDSHAKE_SIZE - RTMP_DIGEST_LENGTH);
+    rand::thread_rng().fill_bytes(random_part);
+    signature_part.copy_from_slice(&hmac_sha256(&key, random_part));
+    buf
+}
+
+/// Drives the server side of a single RTMP handshake, picking between the plain and the
+/// Adobe digest handshake based on C1's version field.
+pub struct DigestHandshake {
+    state: ServerHandshakeState,
+    schema: SchemaVersion,
+}
+
+impl DigestHandshake {
+    pub fn new() -> Self {
+        DigestHandshake {
+            state: ServerHandshakeState::ReadC0C1,
+            schema: SchemaVersion::Unknown,
+        }
+    }
+
+    pub fn state(&self) -> ServerHandshakeState {
+        self.state
+    }
+
+    /// Consumes an incoming C1 (1536 bytes, without the leading C0 version byte) and returns the
+    /// S1+S2 bytes (3072 bytes total) to write back.
+    ///
+    /// A zero version field in C1 (bytes `4..8`) means the client only speaks the plain
+    /// handshake, which this falls back to by echoing C1 back as both S1 and S2. Otherwise both
+    /// digest schemas are tried against C1; [`HandshakeError::DigestMismatch`] is returned if
+    /// neither validates.
+    pub fn process_c1(&mut self, c1: &[u8]) -> Result<Vec<u8>, HandshakeError> {
+        if c1.len() != RTMP_HANDSHAKE_SIZE {
+            return Err(HandshakeError::BadLength(c1.len()));
+        }
+
+        if c1[4..8] == [0, 0, 0, 0] {
+            // Plain handshake: S1 is any 1536 bytes (we just echo C1's layout back), S2 is C1.
+            self.state = ServerHandshakeState::WriteS0S1S2;
+            let mut s1_s2 = Vec::with_capacity(RTMP_HANDSHAKE_SIZE * 2);
+            s1_s2.extend_from_slice(c1);
+            s1_s2.extend_from_slice(c1);
+            return Ok(s1_s2);
+        }
+
+        let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+        buf.copy_from_slice(c1);
+        let (schema, client_digest) = validate_c1(&buf).ok_or(HandshakeError::DigestMismatch)?;
+        self.schema = schema;
+        self.state = ServerHandshakeState::WriteS0S1S2;
+
+        let mut s1_s2 = Vec::with_capacity(RTMP_HANDSHAKE_SIZE * 2);
+        s1_s2.extend_from_slice(&build_s1(schema));
+        s1_s2.extend_from_slice(&build_s2(&client_digest));
+        Ok(s1_s2)
+    }
+
+    /// Consumes C2, completing the handshake.
+    pub fn process_c2(&mut self, c2: &[u8]) -> Result<(), HandshakeError> {
+        if c2.len() != RTMP_HANDSHAKE_SIZE {
+            return Err(HandshakeError::BadLength(c2.len()));
+        }
+        self.state = ServerHandshakeState::Finish;
+        Ok(())
+    }
+}
+
+impl Default for DigestHandshake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_offset_reads_the_blocks_first_four_bytes() {
+        let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+        buf[SCHEMA0_DIGEST_BLOCK_START] = 1;
+        buf[SCHEMA0_DIGEST_BLOCK_START + 1] = 2;
+        buf[SCHEMA0_DIGEST_BLOCK_START + 2] = 3;
+        buf[SCHEMA0_DIGEST_BLOCK_START + 3] = 4;
+
+        // The block's last 4 bytes belong to the unrelated DH key block; set them to a sum that
+        // would produce a different offset, so a regression back to reading them is caught here.
+        let block_end = SCHEMA0_DIGEST_BLOCK_START + DIGEST_BLOCK_LENGTH;
+        buf[block_end - 4..block_end].copy_from_slice(&[100, 100, 100, 100]);
+
+        let offset = digest_offset(&buf, SCHEMA0_DIGEST_BLOCK_START);
+        assert_eq!(offset, SCHEMA0_DIGEST_BLOCK_START + 4 + (1 + 2 + 3 + 4));
+    }
+
+    #[test]
+    fn validate_c1_accepts_a_correctly_embedded_client_digest() {
+        use super::super::define::RTMP_CLIENT_VERSION;
+
+        // Mirrors how a real client builds C1: arbitrary version/random bytes, then the
+        // HMAC-SHA256 digest embedded at the offset those bytes themselves determine.
+        let schema = SchemaVersion::Schema0;
+        let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+        buf[4..8].copy_from_slice(&RTMP_CLIENT_VERSION);
+        for (i, b) in buf[8..].iter_mut().enumerate() {
+            *b = (i % 256) as u8;
+        }
+
+        let block_start = digest_block_start(schema).unwrap();
+        let offset = digest_offset(&buf, block_start);
+        let message = bytes_excluding_digest(&buf, offset);
+        let digest = hmac_sha256(RTMP_CLIENT_KEY_FIRST_HALF.as_bytes(), &message);
+        buf[offset..offset + RTMP_DIGEST_LENGTH].copy_from_slice(&digest);
+
+        let (validated_schema, validated_digest) =
+            validate_c1(&buf).expect("a correctly embedded digest must validate");
+        assert_eq!(validated_schema, schema);
+        assert_eq!(validated_digest, digest);
+    }
+
+    #[test]
+    fn validate_c1_rejects_a_tampered_digest() {
+        let schema = SchemaVersion::Schema1;
+        let mut buf = [0u8; RTMP_HANDSHAKE_SIZE];
+        buf[4..8].copy_from_slice(&RTMP_SERVER_VERSION);
+
+        let block_start = digest_block_start(schema).unwrap();
+        let offset = digest_offset(&buf, block_start);
+        let message = bytes_excluding_digest(&buf, offset);
+        let digest = hmac_sha256(RTMP_CLIENT_KEY_FIRST_HALF.as_bytes(), &message);
+        buf[offset..offset + RTMP_DIGEST_LENGTH].copy_from_slice(&digest);
+
+        // Flip a single byte of the embedded digest.
+        buf[offset] ^= 0xFF;
+
+        assert!(validate_c1(&buf).is_none());
+    }
+}